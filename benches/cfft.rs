@@ -0,0 +1,93 @@
+// cargo bench --bench cfft [filter]
+// no harness and no dependencies, just the best of a few timed batches per length. with
+// --features baseline the radix passes go one complex at a time instead of in lane blocks, run
+// both and hold the columns against each other
+
+use palmfft::{Algorithm, CfftPlan, Complex, Float, Norm, PlanBuilder};
+use std::{hint::black_box, time::Instant};
+
+fn bench<T: Float>(name: &str, n: usize) {
+    let plan = CfftPlan::<T>::new(n);
+    let mut data: Vec<Complex<T>> = (0..n).map(|i| Complex::new(T::from_f64((i % 7) as f64 - 3.0), T::from_f64((i % 5) as f64 - 2.0))).collect();
+    let mut scratch = vec![Complex::new(T::ZERO, T::ZERO); plan.scratch_len()];
+
+    let reps = (2_000_000 / n).max(4);
+    let mut best = f64::INFINITY;
+    for _ in 0..15 {
+        let t = Instant::now();
+        for _ in 0..reps {
+            plan.forward_with_scratch(black_box(&mut data), &mut scratch, Norm::Ortho).unwrap();
+            plan.backward_with_scratch(black_box(&mut data), &mut scratch, Norm::Ortho).unwrap();
+        }
+        best = best.min(t.elapsed().as_secs_f64() / (2 * reps) as f64);
+    }
+    // the usual 5 n log2 n flop count, only comparable between lengths of the same algorithm
+    let mflops = 5.0 * n as f64 * (n as f64).log2() / best / 1e6;
+    println!("{name:>5} {n:>8} {:>12.3} us {mflops:>10.0} mflops", best * 1e6);
+}
+
+// the same round trip on split re and im arrays
+fn bench_split(n: usize) {
+    let plan = CfftPlan::<f64>::new(n);
+    let mut re: Vec<f64> = (0..n).map(|i| (i % 7) as f64 - 3.0).collect();
+    let mut im: Vec<f64> = (0..n).map(|i| (i % 5) as f64 - 2.0).collect();
+    let mut scratch = vec![Complex::new(0.0, 0.0); plan.split_scratch_len()];
+
+    let reps = (2_000_000 / n).max(4);
+    let mut best = f64::INFINITY;
+    for _ in 0..15 {
+        let t = Instant::now();
+        for _ in 0..reps {
+            plan.forward_split_with_scratch(black_box(&mut re), black_box(&mut im), &mut scratch, Norm::Ortho).unwrap();
+            plan.backward_split_with_scratch(black_box(&mut re), black_box(&mut im), &mut scratch, Norm::Ortho).unwrap();
+        }
+        best = best.min(t.elapsed().as_secs_f64() / (2 * reps) as f64);
+    }
+    let mflops = 5.0 * n as f64 * (n as f64).log2() / best / 1e6;
+    println!("split {n:>8} {:>12.3} us {mflops:>10.0} mflops", best * 1e6);
+}
+
+// good-thomas against what else can do the length. it wins where one factor is a prime too large
+// for a cooley-tukey pass, which is the only place the planner picks it, and loses to plain
+// cooley-tukey on smooth lengths. reps are sized off one round trip, a large radix pass is slow
+fn bench_gt(n: usize) {
+    let mut data: Vec<Complex> = (0..n).map(|i| Complex::new((i % 7) as f64 - 3.0, (i % 5) as f64 - 2.0)).collect();
+    let mut line = format!("   gt {n:>8}");
+    for algorithm in [Algorithm::Gt, Algorithm::Bs, Algorithm::Ct] {
+        let plan = PlanBuilder::new(n).algorithm(algorithm).build::<f64>().unwrap();
+        let mut scratch = vec![Complex::new(0.0, 0.0); plan.scratch_len()];
+        let t = Instant::now();
+        plan.forward_with_scratch(black_box(&mut data), &mut scratch, Norm::Ortho).unwrap();
+        let reps = ((2e-3 / t.elapsed().as_secs_f64()) as usize).clamp(1, 2_000_000 / n);
+
+        let mut best = f64::INFINITY;
+        for _ in 0..15 {
+            let t = Instant::now();
+            for _ in 0..reps {
+                plan.forward_with_scratch(black_box(&mut data), &mut scratch, Norm::Ortho).unwrap();
+                plan.backward_with_scratch(black_box(&mut data), &mut scratch, Norm::Ortho).unwrap();
+            }
+            best = best.min(t.elapsed().as_secs_f64() / (2 * reps) as f64);
+        }
+        line += &format!(" {:>10.3} us {algorithm:?}", best * 1e6);
+    }
+    println!("{line}");
+}
+
+fn main() {
+    let filter = std::env::args().skip(1).find(|a| !a.starts_with('-'));
+    println!("ido loop: {}", if cfg!(feature = "baseline") { "one complex at a time (baseline)" } else { "lane blocks" });
+    let lengths = [64, 256, 1000, 1024, 2310, 4096, 16384, 65536, 100000, 1 << 20];
+    for n in lengths {
+        if filter.as_deref().is_some_and(|f| !format!("{n}").contains(f)) { continue; }
+        bench::<f64>("f64", n);
+        bench::<f32>("f32", n);
+        bench_split(n);
+    }
+
+    // smooth, then 2 x 73, 3 x 67, 2 x 727, 2 x 1009 and 3 x 1009
+    for n in [720, 1155, 146, 201, 1454, 2018, 3027] {
+        if filter.as_deref().is_some_and(|f| !format!("{n}").contains(f)) { continue; }
+        bench_gt(n);
+    }
+}
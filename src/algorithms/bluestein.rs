@@ -1,32 +1,39 @@
 use crate::{
-    algorithms::cooleytukey::CooleyTukey, math::{good_size, sincos_2pibyn}, Complex, Result
+    algorithms::{cooleytukey::CooleyTukey, info::PlanInfo, Algorithm, Shared},
+    error::{check_finite, check_len, check_scratch},
+    math::{cost_guess, good_size, sincos_2pibyn}, Complex, Float, Result
 };
 
 use alloc::vec::Vec;
 
-pub struct Bluestein {
+pub struct Bluestein<T = f64> {
     n: usize,
     n2: usize,
-    plan: CooleyTukey,
-    bk: Vec<Complex>,
-    bkf: Vec<Complex>
+    plan: Shared<CooleyTukey<T>>, // may be shared with other plans through a planner
+    bk: Vec<Complex<T>>,
+    bkf: Vec<Complex<T>>
 }
 
-impl Bluestein {
-    pub fn new(length: usize) -> Bluestein {
+impl<T: Float> Bluestein<T> {
+    pub fn new(length: usize) -> Bluestein<T> { Self::with_inner(length, Shared::new(CooleyTukey::new(Self::inner_len(length)))) }
+
+    // length of the cooley-tukey plan doing the convolution
+    pub fn inner_len(length: usize) -> usize { if length == 0 { 0 } else { good_size(length * 2 - 1) } }
+
+    pub fn with_inner(length: usize, inner: Shared<CooleyTukey<T>>) -> Bluestein<T> {
         let n = length;
-        let n2 = if length == 0 { 0 } else { good_size(n * 2 - 1) };
+        let n2 = Self::inner_len(n);
         let mut plan = Bluestein {
-            n, n2, plan: CooleyTukey::new(n2),
-            bk: alloc::vec![Complex::new(0.0, 0.0); n],
-            bkf: alloc::vec![Complex::new(0.0, 0.0); n2]
+            n, n2, plan: inner,
+            bk: alloc::vec![Complex::new(T::ZERO, T::ZERO); n],
+            bkf: alloc::vec![Complex::new(T::ZERO, T::ZERO); n2]
         };
 
         if plan.n < 2 { return plan; }
 
-        let mut tmp = alloc::vec![Complex::new(0.0, 0.0); n * 2];
+        let mut tmp = alloc::vec![Complex::new(T::ZERO, T::ZERO); n * 2];
         sincos_2pibyn(n * 2, &mut tmp);
-        plan.bk[0] = Complex::new(1.0, 0.0);
+        plan.bk[0] = Complex::new(T::ONE, T::ZERO);
 
         let mut coeff = 0;
         for m in 1..n {
@@ -35,7 +42,7 @@ impl Bluestein {
             plan.bk[m] = tmp[coeff];
         }
 
-        let xn2 = 1.0 / (n2 as f64);
+        let xn2 = T::from_f64(1.0 / (n2 as f64));
         plan.bkf[0] = plan.bk[0] * xn2;
 
         for m in 1..n {
@@ -43,28 +50,105 @@ impl Bluestein {
             (plan.bkf[m], plan.bkf[n2 - m]) = (norm, norm);
         }
 
-        for m in n..=(n2 - n) { plan.bkf[m] = Complex::new(0.0, 0.0); }
-        plan.plan.forward(&mut plan.bkf, 1.0).unwrap();
+        for m in n..=(n2 - n) { plan.bkf[m] = Complex::new(T::ZERO, T::ZERO); }
+        plan.plan.forward(&mut plan.bkf, T::ONE).unwrap();
         return plan;
     }
 
-    pub fn forward(&self, data: &mut [Complex], fct: f64) -> Result { return self.fft(data, fct, -1); }
-    pub fn backward(&self, data: &mut [Complex], fct: f64) -> Result { return self.fft(data, fct, 1); }
+    pub fn forward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, -1); }
+    pub fn backward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, 1); }
+
+    pub fn forward_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_with_scratch(data, scratch, fct, -1); }
+    pub fn backward_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_with_scratch(data, scratch, fct, 1); }
 
-    pub fn fft(&self, data: &mut [Complex], fct: f64, sign: i8) -> Result {
-        if self.n != data.len() { return Err(()); }
-        if self.n < 2 { return Ok(()); }
-        let mut akf = alloc::vec![Complex::new(0.0, 0.0); self.n2];
+    pub fn fft(&self, data: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+        return self.fft_with_scratch(data, &mut scratch, fct, sign);
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    pub fn fft_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        check_len(self.n, data.len())?;
+        check_scratch(self.scratch_len(), scratch.len())?;
+        check_finite(data)?;
+        if self.n < 2 {
+            if fct != T::ONE { data.iter_mut().for_each(|d| *d *= fct); }
+            return Ok(());
+        }
+        let (akf, scratch) = scratch.split_at_mut(self.n2);
 
         for m in 0..self.n { akf[m] = data[m] * if sign > 0 { self.bk[m] } else { self.bk[m].conj() }; }
-        for m in self.n..self.n2 { akf[m] = Complex::new(0.0, 0.0); }
+        for m in self.n..self.n2 { akf[m] = Complex::new(T::ZERO, T::ZERO); }
 
-        self.plan.forward(&mut akf, fct)?;
+        self.plan.forward_with_scratch(akf, scratch, fct)?;
         for m in 0..self.n2 { akf[m] *= if sign > 0 { self.bkf[m].conj() } else { self.bkf[m] }; }
-        self.plan.backward(&mut akf, fct)?;
+        self.plan.backward_with_scratch(akf, scratch, T::ONE)?;
         for m in 0..self.n { data[m] = akf[m] * if sign > 0 { self.bk[m] } else { self.bk[m].conj() }; }
         return Ok(());
     }
 
+    pub fn forward_real(&self, input: &[T], output: &mut [Complex<T>], fct: T) -> Result {
+        check_len(self.n, input.len())?;
+        check_len(self.n / 2 + 1, output.len())?;
+        if self.n == 0 { output[0] = Complex::new(T::ZERO, T::ZERO); return Ok(()); }
+        let mut tmp: Vec<Complex<T>> = input.iter().map(|&x| Complex::new(x, T::ZERO)).collect();
+        self.forward(&mut tmp, fct)?;
+        output.copy_from_slice(&tmp[..output.len()]);
+        return Ok(());
+    }
+
+    pub fn backward_real(&self, input: &[Complex<T>], output: &mut [T], fct: T) -> Result {
+        check_len(self.n / 2 + 1, input.len())?;
+        check_len(self.n, output.len())?;
+        if self.n == 0 { return Ok(()); }
+        let mut tmp = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.n];
+        tmp[0] = Complex::new(input[0].re, T::ZERO);
+        for m in 1..input.len() { (tmp[m], tmp[self.n - m]) = (input[m], input[m].conj()); }
+        if self.n & 1 == 0 { tmp[self.n / 2] = Complex::new(input[self.n / 2].re, T::ZERO); }
+
+        self.backward(&mut tmp, fct)?;
+        for m in 0..self.n { output[m] = tmp[m].re; }
+        return Ok(());
+    }
+
     pub fn len(&self) -> usize { self.n }
-}
\ No newline at end of file
+    pub fn scratch_len(&self) -> usize { if self.n < 2 { 0 } else { self.n2 + self.plan.scratch_len() } }
+
+    // two transforms of n2 plus the chirps and the pointwise product, costed as a third
+    pub fn describe(&self) -> PlanInfo {
+        let bytes = (self.bk.len() + self.bkf.len()) * core::mem::size_of::<Complex<T>>();
+        return PlanInfo {
+            algorithm: Algorithm::Bs, len: self.n, real: false, factors: Vec::new(), padded_len: Some(self.n2),
+            twiddle_bytes: bytes, flops: 3.0 * cost_guess(self.n2), children: alloc::vec![self.plan.describe()]
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{complex_signal, dft, err};
+
+    #[test]
+    fn matches_dft_with_scale() {
+        for n in [2, 3, 5, 17, 100, 167, 347] {
+            let x = complex_signal(n, n as u64);
+            let plan = Bluestein::<f64>::new(n);
+            let mut y = x.clone();
+            plan.forward(&mut y, 0.5).unwrap();
+            let want: alloc::vec::Vec<Complex> = dft(&x, -1).iter().map(|&v| v * 0.5).collect();
+            assert!(err(&y, &want) < 1e-13, "n = {}", n);
+            plan.backward(&mut y, 2.0 / n as f64).unwrap();
+            assert!(err(&y, &x) < 1e-13, "n = {}", n);
+        }
+    }
+
+    // a single element is its own transform, but the scale still applies
+    #[test]
+    fn one_element_is_scaled() {
+        let mut data = [Complex::new(3.0, -1.0)];
+        Bluestein::<f64>::new(1).forward(&mut data, 0.5).unwrap();
+        assert_eq!(data, [Complex::new(1.5, -0.5)]);
+        Bluestein::<f64>::new(0).backward(&mut [], 0.5).unwrap();
+    }
+}
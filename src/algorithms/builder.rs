@@ -0,0 +1,108 @@
+use crate::{
+    algorithms::{
+        bluestein::Bluestein, check_algorithm, cooleytukey::CooleyTukey, realcooleytukey::RealCooleyTukey,
+        Algorithm, CfftPlan, RfftPlan, Shared
+    },
+    FftError, Float, Result
+};
+
+use alloc::vec::Vec;
+
+// plans with the choices the cost model would otherwise make. factors are the cooley-tukey
+// radices in pass order and imply cooley-tukey when no algorithm is given
+#[derive(Clone, Debug, Default)]
+pub struct PlanBuilder {
+    length: usize,
+    algorithm: Option<Algorithm>,
+    factors: Option<Vec<usize>>
+}
+
+impl PlanBuilder {
+    pub fn new(length: usize) -> Self { PlanBuilder { length, algorithm: None, factors: None } }
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self { self.algorithm = Some(algorithm); return self; }
+    pub fn factors(mut self, factors: &[usize]) -> Self { self.factors = Some(factors.to_vec()); return self; }
+
+    fn resolve(&self) -> Result<Option<Algorithm>> {
+        match (self.algorithm, &self.factors) {
+            (Some(algorithm), Some(_)) if algorithm != Algorithm::Ct => Err(FftError::InvalidParameter("factors only apply to cooley-tukey")),
+            (None, Some(_)) => Ok(Some(Algorithm::Ct)),
+            (algorithm, _) => Ok(algorithm)
+        }
+    }
+
+    pub fn build<T: Float>(&self) -> Result<CfftPlan<T>> {
+        let algorithm = match self.resolve()? {
+            Some(algorithm) => algorithm,
+            None => return Ok(CfftPlan::new(self.length))
+        };
+        check_algorithm(self.length, algorithm)?;
+        if let Some(factors) = &self.factors { return Ok(CfftPlan::Ct(Shared::new(CooleyTukey::with_factors(self.length, factors)?))); }
+        return Ok(CfftPlan::with_algorithm(self.length, algorithm, &mut |n| Shared::new(CooleyTukey::new(n))));
+    }
+
+    // real transforms only come as cooley-tukey or bluestein
+    pub fn build_real<T: Float>(&self) -> Result<RfftPlan<T>> {
+        match (self.resolve()?, &self.factors) {
+            (None, _) => Ok(RfftPlan::new(self.length)),
+            (Some(Algorithm::Ct), Some(factors)) => Ok(RfftPlan::Ct(RealCooleyTukey::with_factors(self.length, factors)?)),
+            (Some(Algorithm::Ct), None) => Ok(RfftPlan::Ct(RealCooleyTukey::new(self.length))),
+            (Some(Algorithm::Bs), _) => Ok(RfftPlan::Bs(Bluestein::new(self.length))),
+            _ => Err(FftError::InvalidParameter("real plans are cooley-tukey or bluestein"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::{complex_signal, dft, err, real_to_complex, signal}, Norm};
+
+    fn check(plan: &CfftPlan, algorithm: Algorithm) {
+        assert_eq!(plan.describe().algorithm, algorithm);
+        let x = complex_signal(plan.len(), 5);
+        let mut y = x.clone();
+        plan.forward(&mut y, Norm::Backward).unwrap();
+        assert!(err(&y, &dft(&x, -1)) < 1e-13, "{:?} {}", algorithm, plan.len());
+    }
+
+    #[test]
+    fn forced_algorithms() {
+        for (n, algorithms) in [(1009, &[Algorithm::Ct, Algorithm::Bs, Algorithm::Rd][..]), (2018, &[Algorithm::Ct, Algorithm::Bs, Algorithm::Gt]), (64, &[Algorithm::Bs])] {
+            for &algorithm in algorithms { check(&PlanBuilder::new(n).algorithm(algorithm).build().unwrap(), algorithm); }
+        }
+        check(&PlanBuilder::new(1009).build().unwrap(), CfftPlan::<f64>::new(1009).describe().algorithm);
+    }
+
+    #[test]
+    fn chosen_factors() {
+        for factors in [&[5, 2, 3, 2][..], &[3, 4, 5], &[15, 4]] {
+            let plan = PlanBuilder::new(60).factors(factors).build().unwrap();
+            assert_eq!(plan.describe().factors, factors);
+            check(&plan, Algorithm::Ct);
+        }
+
+        let x = signal(60, 9);
+        let want = &dft(&real_to_complex(&x), -1)[..31];
+        for factors in [&[4, 3, 5][..], &[2, 2, 15]] {
+            let plan = PlanBuilder::new(60).algorithm(Algorithm::Ct).factors(factors).build_real::<f64>().unwrap();
+            assert_eq!(plan.describe().factors, factors);
+            let mut y = alloc::vec![crate::Complex::new(0.0, 0.0); 31];
+            plan.forward(&x, &mut y, Norm::Backward).unwrap();
+            assert!(err(&y, want) < 1e-13, "{:?}", factors);
+        }
+    }
+
+    #[test]
+    fn rejected() {
+        let bad = |b: PlanBuilder| matches!(b.build::<f64>(), Err(FftError::InvalidParameter(_)));
+        assert!(bad(PlanBuilder::new(60).algorithm(Algorithm::Rd)));
+        assert!(bad(PlanBuilder::new(64).algorithm(Algorithm::Gt)));
+        assert!(bad(PlanBuilder::new(60).algorithm(Algorithm::Bs).factors(&[4, 3, 5])));
+        assert!(bad(PlanBuilder::new(60).factors(&[4, 3, 4])));
+        assert!(bad(PlanBuilder::new(60).factors(&[6, 10])));
+
+        let bad_real = |b: PlanBuilder| matches!(b.build_real::<f64>(), Err(FftError::InvalidParameter(_)));
+        assert!(bad_real(PlanBuilder::new(61).algorithm(Algorithm::Rd)));
+        assert!(bad_real(PlanBuilder::new(60).factors(&[3, 4, 5])));
+    }
+}
@@ -1,590 +1,423 @@
 use crate::{
-    math::sincos_2pibyn, Complex, ComplexExt, Result
+    algorithms::{info::PlanInfo, Algorithm},
+    error::{check_finite, check_finite_real, check_len, check_scratch},
+    math::{cost_guess, sincos_2pibyn}, Complex, ComplexExt, FftError, Float, Result
 };
 
 use alloc::vec::Vec;
+use core::ops::{Add, Sub};
 
-fn pmc(a: Complex, b: Complex) -> (Complex, Complex) { (a + b, a - b) }
+fn pmc<T: Float, V: Lane<T>>(a: V, b: V) -> (V, V) { (a + b, a - b) }
 
-fn pass2(ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8) {
-    let cdim = 2;
-    if ido == 1 {
-        for k in 0..l1 {
-            (ch[k], ch[k + l1]) = pmc(cc[k * cdim], cc[k * cdim + 1]);
-        }
-    }
-    else {
-        for k in 0..l1 {
-            (ch[k * ido], ch[(k + l1) * ido]) = pmc(cc[k * cdim * ido], cc[k * cdim * ido + 1 * ido]);
+// how many complexes the ido loop works on at once. split into re and im arrays of this width,
+// the butterflies turn into plain elementwise arithmetic that llvm vectorizes without help
+const LANES: usize = 4;
 
-            for i in 1..ido {
-                ch[i + k * ido] = cc[i + k * cdim * ido] + cc[i + (k * cdim + 1) * ido];
-                let twiddle = if sign > 0 { wa[i - 1] } else { wa[i - 1].conj() };
-                ch[i + (k + l1) * ido] = twiddle * (cc[i + k * cdim * ido] - cc[i + (k * cdim + 1) * ido]);
-            }
-        }
-    }
+// what a butterfly gets fed: a single complex, or a block of LANES of them
+trait Lane<T: Float>: Copy + Add<Output = Self> + Sub<Output = Self> {
+    fn scale(self, s: T) -> Self;
+    fn rot90(self) -> Self;
+    fn rotm90(self) -> Self;
+    // w * self going backward, w.conj() * self going forward
+    fn twiddle(self, w: Self, sign: i8) -> Self;
 }
 
-fn pass3(ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8) {
-    let cdim = 3;
-    const TW1R: f64 = -0.5;
-    let tw1i = (sign as f64) * 0.86602540378443864676;
+impl<T: Float> Lane<T> for Complex<T> {
+    #[inline(always)] fn scale(self, s: T) -> Self { self * s }
+    #[inline(always)] fn rot90(self) -> Self { ComplexExt::rot90(&self) }
+    #[inline(always)] fn rotm90(self) -> Self { ComplexExt::rotm90(&self) }
+    #[inline(always)] fn twiddle(self, w: Self, sign: i8) -> Self { if sign < 0 { w.conj() * self } else { w * self } }
+}
 
-    if ido == 1 {
-        for k in 0..l1 {
-            let t0 = cc[k * cdim];
-            let (t1, t2) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (2 + k * cdim)]);
+#[derive(Clone, Copy)]
+struct Block<T> {
+    re: [T; LANES],
+    im: [T; LANES]
+}
 
-            ch[0 + ido * (k + l1 * 0)] = t0 + t1;
+impl<T: Float> Block<T> {
+    #[inline(always)]
+    fn load(x: &[Complex<T>]) -> Self {
+        let x = &x[..LANES];
+        return Block { re: core::array::from_fn(|l| x[l].re), im: core::array::from_fn(|l| x[l].im) };
+    }
 
-            let ca = Complex::new(t0.re + TW1R * t1.re, t0.im + TW1R * t1.im);
-            let cb = Complex::new(-tw1i * t2.im, tw1i * t2.re);
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 2)]) = pmc(ca, cb);
-        }
+    #[inline(always)]
+    fn store(self, x: &mut [Complex<T>]) {
+        for (l, c) in x[..LANES].iter_mut().enumerate() { *c = Complex::new(self.re[l], self.im[l]); }
     }
-    else {
-        for k in 0..l1 {
-            let t0 = cc[0 + ido * (0 + k * cdim)];
-            let (t1, t2) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (2 + k * cdim)]);
 
-            ch[0 + ido * (k + l1 * 0)] = t0 + t1;
+    #[inline(always)]
+    fn map(a: [T; LANES], b: [T; LANES], f: impl Fn(T, T) -> T) -> [T; LANES] { core::array::from_fn(|l| f(a[l], b[l])) }
+}
 
-            let ca = Complex::new(t0.re + TW1R * t1.re, t0.im + TW1R * t1.im);
-            let cb = Complex::new(-tw1i * t2.im, tw1i * t2.re);
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 2)]) = pmc(ca, cb);
+impl<T: Float> Add for Block<T> {
+    type Output = Self;
+    #[inline(always)] fn add(self, rhs: Self) -> Self { Block { re: Block::map(self.re, rhs.re, |a, b| a + b), im: Block::map(self.im, rhs.im, |a, b| a + b) } }
+}
 
-            for i in 1..ido {
-                let t1_in = cc[i + ido * (1 + k * cdim)];
-                let t2_in = cc[i + ido * (2 + k * cdim)];
-                let t0 = cc[i + ido * (0 + k * cdim)];
-                let (t1, t2) = pmc(t1_in, t2_in);
+impl<T: Float> Sub for Block<T> {
+    type Output = Self;
+    #[inline(always)] fn sub(self, rhs: Self) -> Self { Block { re: Block::map(self.re, rhs.re, |a, b| a - b), im: Block::map(self.im, rhs.im, |a, b| a - b) } }
+}
 
-                ch[i + ido * (k + l1 * 0)] = t0 + t1;
+// what a pass reads from: interleaved complexes, or re and im in arrays of their own
+trait Src<T: Float>: Copy + Send + Sync {
+    fn at(self, i: usize) -> Complex<T>;
+    fn block(self, i: usize) -> Block<T>;
+}
 
-                let ca = Complex::new(t0.re + TW1R * t1.re, t0.im + TW1R * t1.im);
-                let cb = Complex::new(-tw1i * t2.im, tw1i * t2.re);
-                let (da, db) = pmc(ca, cb);
+// and what it writes to, cut into rows that may go to other threads
+trait Dst<T: Float>: Default + Send {
+    fn len(&self) -> usize;
+    fn put(&mut self, i: usize, c: Complex<T>);
+    fn put_block(&mut self, i: usize, b: Block<T>);
+    fn split(self, mid: usize) -> (Self, Self);
+}
 
-                if sign < 0 {
-                    ch[i + ido * (k + l1 * 1)] = wa[i - 1 + 0 * (ido - 1)].conj() * da;
-                    ch[i + ido * (k + l1 * 2)] = wa[i - 1 + 1 * (ido - 1)].conj() * db;
-                }
-                else {
-                    ch[i + ido * (k + l1 * 1)] = wa[i - 1 + 0 * (ido - 1)] * da;
-                    ch[i + ido * (k + l1 * 2)] = wa[i - 1 + 1 * (ido - 1)] * db;
-                }
-            }
-        }
-    }
+impl<T: Float> Src<T> for &[Complex<T>] {
+    #[inline(always)] fn at(self, i: usize) -> Complex<T> { self[i] }
+    #[inline(always)] fn block(self, i: usize) -> Block<T> { Block::load(&self[i..]) }
 }
 
-fn pass4(ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8) {
-    let cdim = 4;
+impl<T: Float> Dst<T> for &mut [Complex<T>] {
+    #[inline(always)] fn len(&self) -> usize { <[Complex<T>]>::len(self) }
+    #[inline(always)] fn put(&mut self, i: usize, c: Complex<T>) { self[i] = c; }
+    #[inline(always)] fn put_block(&mut self, i: usize, b: Block<T>) { b.store(&mut self[i..]); }
+    #[inline(always)] fn split(self, mid: usize) -> (Self, Self) { self.split_at_mut(mid) }
+}
 
-    if ido == 1 {
-        for k in 0..l1 {
-            let (t2, t1) = pmc(cc[k * cdim], cc[k * cdim + 2]);
-            let (t3, mut t4) = pmc(cc[k * cdim + 1], cc[k * cdim + 3]);
+// split arrays load straight into a block, no shuffling at all
+impl<T: Float> Src<T> for (&[T], &[T]) {
+    #[inline(always)] fn at(self, i: usize) -> Complex<T> { Complex::new(self.0[i], self.1[i]) }
 
-            t4 = if sign < 0 { t4.rotm90() } else { t4.rot90() };
+    #[inline(always)]
+    fn block(self, i: usize) -> Block<T> {
+        let (re, im) = (&self.0[i..i + LANES], &self.1[i..i + LANES]);
+        return Block { re: core::array::from_fn(|l| re[l]), im: core::array::from_fn(|l| im[l]) };
+    }
+}
 
-            (ch[0 + ido * (k + l1 * 0)], ch[0 + ido * (k + l1 * 2)]) = pmc(t2, t3);
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 3)]) = pmc(t1, t4);
-        }
+impl<T: Float> Dst<T> for (&mut [T], &mut [T]) {
+    #[inline(always)] fn len(&self) -> usize { self.0.len() }
+    #[inline(always)] fn put(&mut self, i: usize, c: Complex<T>) { (self.0[i], self.1[i]) = (c.re, c.im); }
+
+    #[inline(always)]
+    fn put_block(&mut self, i: usize, b: Block<T>) {
+        self.0[i..i + LANES].copy_from_slice(&b.re);
+        self.1[i..i + LANES].copy_from_slice(&b.im);
     }
-    else {
-        for k in 0..l1 {
-            let (t2, t1) = pmc(cc[k * cdim * ido], cc[k * cdim * ido + 2 * ido]);
-            let (t3, mut t4) = pmc(cc[k * cdim * ido + ido], cc[k * cdim * ido + 3 * ido]);
 
-            t4 = if sign < 0 { t4.rotm90() } else { t4.rot90() };
+    #[inline(always)]
+    fn split(self, mid: usize) -> (Self, Self) {
+        let ((r0, r1), (i0, i1)) = (self.0.split_at_mut(mid), self.1.split_at_mut(mid));
+        return ((r0, i0), (r1, i1));
+    }
+}
 
-            (ch[0 + ido * (k + l1 * 0)], ch[0 + ido * (k + l1 * 2)]) = pmc(t2, t3);
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 3)]) = pmc(t1, t4);
+// same operations in the same order as the complex versions, so both give identical bits
+impl<T: Float> Lane<T> for Block<T> {
+    #[inline(always)] fn scale(self, s: T) -> Self { Block { re: self.re.map(|a| a * s), im: self.im.map(|a| a * s) } }
+    #[inline(always)] fn rot90(self) -> Self { Block { re: self.im.map(|a| -a), im: self.re } }
+    #[inline(always)] fn rotm90(self) -> Self { Block { re: self.im, im: self.re.map(|a| -a) } }
+
+    #[inline(always)]
+    fn twiddle(self, w: Self, sign: i8) -> Self {
+        let (wr, wi, dr, di) = (w.re, w.im, self.re, self.im);
+        return if sign < 0 {
+            Block { re: core::array::from_fn(|l| wr[l] * dr[l] + wi[l] * di[l]), im: core::array::from_fn(|l| wr[l] * di[l] - wi[l] * dr[l]) }
+        }
+        else {
+            Block { re: core::array::from_fn(|l| wr[l] * dr[l] - wi[l] * di[l]), im: core::array::from_fn(|l| wr[l] * di[l] + wi[l] * dr[l]) }
+        };
+    }
+}
 
-            for i in 1..ido {
-                let (t2, t1) = pmc(cc[i + k * cdim * ido], cc[i + k * cdim * ido + 2 * ido]);
-                let (t3, mut t4) = pmc(cc[i + k * cdim * ido + ido], cc[i + k * cdim * ido + 3 * ido]);
+// the radix r butterfly without twiddles, written once for both lane types
+trait Butterfly<T: Float, const R: usize> {
+    fn apply<V: Lane<T>>(&self, x: [V; R]) -> [V; R];
+}
 
-                t4 = if sign < 0 { t4.rotm90() } else { t4.rot90() };
+// cos(2 pi j / r) and sign * sin(2 pi j / r) for j in 1..=h, h = r / 2
+struct Radix<T, const H: usize> {
+    twr: [T; H],
+    twi: [T; H],
+    sign: i8
+}
 
-                let (c0, c3) = pmc(t2, t3);
-                let (c2, c4) = pmc(t1, t4);
+impl<T: Float, const H: usize> Radix<T, H> {
+    fn new(cos: [f64; H], sin: [f64; H], sign: i8) -> Self {
+        return Radix { twr: cos.map(T::from_f64), twi: sin.map(|s| T::from_f64((sign as f64) * s)), sign };
+    }
+}
 
-                ch[i + ido * (k + l1 * 0)] = c0;
-                if sign < 0 {
-                    ch[i + ido * (k + l1 * 1)] = wa[i - 1 + 0 * (ido - 1)].conj() * c2;
-                    ch[i + ido * (k + l1 * 2)] = wa[i - 1 + 1 * (ido - 1)].conj() * c3;
-                    ch[i + ido * (k + l1 * 3)] = wa[i - 1 + 2 * (ido - 1)].conj() * c4;
-                }
-                else {
-                    ch[i + ido * (k + l1 * 1)] = wa[i - 1 + 0 * (ido - 1)] * c2;
-                    ch[i + ido * (k + l1 * 2)] = wa[i - 1 + 1 * (ido - 1)] * c3;
-                    ch[i + ido * (k + l1 * 3)] = wa[i - 1 + 2 * (ido - 1)] * c4;
-                }
-            }
-        }
+impl<T: Float> Butterfly<T, 2> for Radix<T, 0> {
+    #[inline(always)]
+    fn apply<V: Lane<T>>(&self, x: [V; 2]) -> [V; 2] {
+        let (y0, y1) = pmc(x[0], x[1]);
+        return [y0, y1];
     }
 }
 
-fn pass5(ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8) {
-    let cdim = 5;
-    const TW1R: f64 = 0.3090169943749474241;
-    const TW2R: f64 = -0.8090169943749474241;
-    let tw1i = (sign as f64) * 0.95105651629515357212;
-    let tw2i = (sign as f64) * 0.58778525229247312917;
+impl<T: Float> Butterfly<T, 3> for Radix<T, 1> {
+    #[inline(always)]
+    fn apply<V: Lane<T>>(&self, x: [V; 3]) -> [V; 3] {
+        let ([tw1r], [tw1i]) = (self.twr, self.twi);
+        let t0 = x[0];
+        let (t1, t2) = pmc(x[1], x[2]);
+
+        let ca = t0 + t1.scale(tw1r);
+        let cb = t2.scale(tw1i).rot90();
+        let (y1, y2) = pmc(ca, cb);
+        return [t0 + t1, y1, y2];
+    }
+}
 
-    if ido == 1 {
-        for k in 0..l1 {
-            let t0 = cc[0 + ido * (0 + k * cdim)];
-            let (t1, t4) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (4 + k * cdim)]);
-            let (t2, t3) = pmc(cc[0 + ido * (2 + k * cdim)], cc[0 + ido * (3 + k * cdim)]);
+impl<T: Float> Butterfly<T, 4> for Radix<T, 0> {
+    #[inline(always)]
+    fn apply<V: Lane<T>>(&self, x: [V; 4]) -> [V; 4] {
+        let (t2, t1) = pmc(x[0], x[2]);
+        let (t3, mut t4) = pmc(x[1], x[3]);
 
-            ch[0 + ido * (k + l1 * 0)] = t0 + t1 + t2;
+        t4 = if self.sign < 0 { t4.rotm90() } else { t4.rot90() };
 
-            let ca = Complex::new(
-                t0.re + TW1R * t1.re + TW2R * t2.re,
-                t0.im + TW1R * t1.im + TW2R * t2.im
-            );
-            let cb = Complex::new(
-                -(tw1i * t4.im + tw2i * t3.im),
-                tw1i * t4.re + tw2i * t3.re
-            );
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 4)]) = pmc(ca, cb);
+        let (y0, y2) = pmc(t2, t3);
+        let (y1, y3) = pmc(t1, t4);
+        return [y0, y1, y2, y3];
+    }
+}
 
-            let ca = Complex::new(
-                t0.re + TW2R * t1.re + TW1R * t2.re,
-                t0.im + TW2R * t1.im + TW1R * t2.im
-            );
-            let cb = Complex::new(
-                -(tw2i * t4.im - tw1i * t3.im),
-                tw2i * t4.re - tw1i * t3.re
-            );
-            (ch[0 + ido * (k + l1 * 2)], ch[0 + ido * (k + l1 * 3)]) = pmc(ca, cb);
-        }
+impl<T: Float> Butterfly<T, 5> for Radix<T, 2> {
+    #[inline(always)]
+    fn apply<V: Lane<T>>(&self, x: [V; 5]) -> [V; 5] {
+        let ([tw1r, tw2r], [tw1i, tw2i]) = (self.twr, self.twi);
+        let t0 = x[0];
+        let (t1, t4) = pmc(x[1], x[4]);
+        let (t2, t3) = pmc(x[2], x[3]);
+
+        let ca = t0 + t1.scale(tw1r) + t2.scale(tw2r);
+        let cb = (t4.scale(tw1i) + t3.scale(tw2i)).rot90();
+        let (y1, y4) = pmc(ca, cb);
+
+        let ca = t0 + t1.scale(tw2r) + t2.scale(tw1r);
+        let cb = (t4.scale(tw2i) - t3.scale(tw1i)).rot90();
+        let (y2, y3) = pmc(ca, cb);
+        return [t0 + t1 + t2, y1, y2, y3, y4];
     }
-    else {
-        for k in 0..l1 {
-            let t0 = cc[0 + ido * (0 + k * cdim)];
-            let (t1, t4) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (4 + k * cdim)]);
-            let (t2, t3) = pmc(cc[0 + ido * (2 + k * cdim)], cc[0 + ido * (3 + k * cdim)]);
+}
 
-            ch[0 + ido * (k + l1 * 0)] = t0 + t1 + t2;
+impl<T: Float> Butterfly<T, 7> for Radix<T, 3> {
+    #[inline(always)]
+    fn apply<V: Lane<T>>(&self, x: [V; 7]) -> [V; 7] {
+        let ([tw1r, tw2r, tw3r], [tw1i, tw2i, tw3i]) = (self.twr, self.twi);
+        let t1 = x[0];
+        let (t2, t7) = pmc(x[1], x[6]);
+        let (t3, t6) = pmc(x[2], x[5]);
+        let (t4, t5) = pmc(x[3], x[4]);
+
+        let ca = t1 + t2.scale(tw1r) + t3.scale(tw2r) + t4.scale(tw3r);
+        let cb = (t7.scale(tw1i) + t6.scale(tw2i) + t5.scale(tw3i)).rot90();
+        let (y1, y6) = pmc(ca, cb);
+
+        let ca = t1 + t2.scale(tw2r) + t3.scale(tw3r) + t4.scale(tw1r);
+        let cb = (t7.scale(tw2i) - t6.scale(tw3i) - t5.scale(tw1i)).rot90();
+        let (y2, y5) = pmc(ca, cb);
+
+        let ca = t1 + t2.scale(tw3r) + t3.scale(tw1r) + t4.scale(tw2r);
+        let cb = (t7.scale(tw3i) - t6.scale(tw1i) + t5.scale(tw2i)).rot90();
+        let (y3, y4) = pmc(ca, cb);
+        return [t1 + t2 + t3 + t4, y1, y2, y3, y4, y5, y6];
+    }
+}
 
-            let ca = Complex::new(
-                t0.re + TW1R * t1.re + TW2R * t2.re,
-                t0.im + TW1R * t1.im + TW2R * t2.im
-            );
-            let cb = Complex::new(
-                -(tw1i * t4.im + tw2i * t3.im),
-                tw1i * t4.re + tw2i * t3.re
-            );
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 4)]) = pmc(ca, cb);
+impl<T: Float> Butterfly<T, 11> for Radix<T, 5> {
+    #[inline(always)]
+    fn apply<V: Lane<T>>(&self, x: [V; 11]) -> [V; 11] {
+        let ([tw1r, tw2r, tw3r, tw4r, tw5r], [tw1i, tw2i, tw3i, tw4i, tw5i]) = (self.twr, self.twi);
+        let t1 = x[0];
+        let (t2, t11) = pmc(x[1], x[10]);
+        let (t3, t10) = pmc(x[2], x[9]);
+        let (t4, t9) = pmc(x[3], x[8]);
+        let (t5, t8) = pmc(x[4], x[7]);
+        let (t6, t7) = pmc(x[5], x[6]);
+
+        let ca = t1 + t2.scale(tw1r) + t3.scale(tw2r) + t4.scale(tw3r) + t5.scale(tw4r) + t6.scale(tw5r);
+        let cb = (t11.scale(tw1i) + t10.scale(tw2i) + t9.scale(tw3i) + t8.scale(tw4i) + t7.scale(tw5i)).rot90();
+        let (y1, y10) = pmc(ca, cb);
+
+        let ca = t1 + t2.scale(tw2r) + t3.scale(tw4r) + t4.scale(tw5r) + t5.scale(tw3r) + t6.scale(tw1r);
+        let cb = (t11.scale(tw2i) + t10.scale(tw4i) - t9.scale(tw5i) - t8.scale(tw3i) - t7.scale(tw1i)).rot90();
+        let (y2, y9) = pmc(ca, cb);
+
+        let ca = t1 + t2.scale(tw3r) + t3.scale(tw5r) + t4.scale(tw2r) + t5.scale(tw1r) + t6.scale(tw4r);
+        let cb = (t11.scale(tw3i) - t10.scale(tw5i) - t9.scale(tw2i) + t8.scale(tw1i) + t7.scale(tw4i)).rot90();
+        let (y3, y8) = pmc(ca, cb);
+
+        let ca = t1 + t2.scale(tw4r) + t3.scale(tw3r) + t4.scale(tw1r) + t5.scale(tw5r) + t6.scale(tw2r);
+        let cb = (t11.scale(tw4i) - t10.scale(tw3i) + t9.scale(tw1i) + t8.scale(tw5i) - t7.scale(tw2i)).rot90();
+        let (y4, y7) = pmc(ca, cb);
+
+        let ca = t1 + t2.scale(tw5r) + t3.scale(tw1r) + t4.scale(tw4r) + t5.scale(tw2r) + t6.scale(tw3r);
+        let cb = (t11.scale(tw5i) - t10.scale(tw1i) + t9.scale(tw4i) - t8.scale(tw2i) + t7.scale(tw3i)).rot90();
+        let (y5, y6) = pmc(ca, cb);
+        return [t1 + t2 + t3 + t4 + t5 + t6, y1, y2, y3, y4, y5, y6, y7, y8, y9, y10];
+    }
+}
 
-            let ca = Complex::new(
-                t0.re + TW2R * t1.re + TW1R * t2.re,
-                t0.im + TW2R * t1.im + TW1R * t2.im
-            );
-            let cb = Complex::new(
-                -(tw2i * t4.im - tw1i * t3.im),
-                tw2i * t4.re - tw1i * t3.re
-            );
-            (ch[0 + ido * (k + l1 * 2)], ch[0 + ido * (k + l1 * 3)]) = pmc(ca, cb);
-
-            for i in 1..ido {
-                let t0 = cc[i + ido * (0 + k * cdim)];
-                let (t1, t4) = pmc(cc[i + ido * (1 + k * cdim)], cc[i + ido * (4 + k * cdim)]);
-                let (t2, t3) = pmc(cc[i + ido * (2 + k * cdim)], cc[i + ido * (3 + k * cdim)]);
-
-                ch[i + ido * (k + l1 * 0)] = t0 + t1 + t2;
-
-                let ca = Complex::new(
-                    t0.re + TW1R * t1.re + TW2R * t2.re,
-                    t0.im + TW1R * t1.im + TW2R * t2.im
-                );
-                let cb = Complex::new(
-                    -(tw1i * t4.im + tw2i * t3.im),
-                    tw1i * t4.re + tw2i * t3.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 1)] = if sign < 0 { wa[i - 1 + 0 * (ido - 1)].conj() * da } else { wa[i - 1 + 0 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 4)] = if sign < 0 { wa[i - 1 + 3 * (ido - 1)].conj() * db } else { wa[i - 1 + 3 * (ido - 1)] * db };
-
-                let ca = Complex::new(
-                    t0.re + TW2R * t1.re + TW1R * t2.re,
-                    t0.im + TW2R * t1.im + TW1R * t2.im
-                );
-                let cb = Complex::new(
-                    -(tw2i * t4.im - tw1i * t3.im),
-                    tw2i * t4.re - tw1i * t3.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 2)] = if sign < 0 { wa[i - 1 + 1 * (ido - 1)].conj() * da } else { wa[i - 1 + 1 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 3)] = if sign < 0 { wa[i - 1 + 2 * (ido - 1)].conj() * db } else { wa[i - 1 + 2 * (ido - 1)] * db };
+// output rows are split into pieces this big at the least before going to other threads
+#[cfg(feature = "parallel")]
+const PARALLEL_GRAIN: usize = 1 << 15;
+
+// one radix r pass. ch is R rows of l1 * ido, and every (k, i) of the pass writes only its own
+// spot in each row, so any range of them can be done on its own, possibly on another thread
+#[allow(clippy::too_many_arguments)]
+fn pass<T: Float, const R: usize>(ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T, bfly: &(impl Butterfly<T, R> + Sync)) {
+    let m = l1 * ido;
+    let mut rest = ch;
+    let rows = core::array::from_fn(|_| {
+        let (head, tail) = core::mem::take(&mut rest).split(m);
+        rest = tail;
+        head
+    });
+
+    #[cfg(feature = "parallel")]
+    {
+        let threads = crate::parallel::split(m * R, PARALLEL_GRAIN);
+        if threads > 1 {
+            let mut rest: [_; R] = rows;
+            let mut jobs = Vec::with_capacity(threads);
+            for t in 0..threads {
+                let range = crate::parallel::chunk(m, threads, t);
+                let part = core::array::from_fn(|j| {
+                    let (head, tail) = core::mem::take(&mut rest[j]).split(range.len());
+                    rest[j] = tail;
+                    head
+                });
+                jobs.push(move || { pass_range(ido, range.start, cc, part, wa, sign, fct, bfly); Ok(()) });
             }
+            let _ = crate::parallel::run(jobs); // passes can't fail
+            return;
         }
     }
+    pass_range(ido, 0, cc, rows, wa, sign, fct, bfly);
 }
 
-fn pass7(ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8) {
-    let cdim = 7;
-    const TW1R: f64 = 0.623489801858733530525;
-    const TW2R: f64 = -0.222520933956314404289;
-    const TW3R: f64 = -0.9009688679024191262361;
-    let tw1i = (sign as f64) * 0.7818314824680298087084;
-    let tw2i = (sign as f64) * 0.9749279121818236070181;
-    let tw3i = (sign as f64) * 0.4338837391175581204758;
-
+// the (k, i) with k * ido + i in start..start + rows[0].len(). the ido == 1 pass (always the
+// last) carries the scale; otherwise i == 0 needs no twiddle, then the rest of the ido loop goes
+// LANES at a time and single complexes mop up whatever is left
+#[allow(clippy::too_many_arguments)]
+fn pass_range<T: Float, const R: usize, D: Dst<T>>(ido: usize, start: usize, cc: impl Src<T>, mut rows: [D; R], wa: &[Complex<T>], sign: i8, fct: T, bfly: &impl Butterfly<T, R>) {
+    let end = start + rows[0].len();
     if ido == 1 {
-        for k in 0..l1 {
-            let t1 = cc[0 + ido * (0 + k * cdim)];
-            let (t2, t7) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (6 + k * cdim)]);
-            let (t3, t6) = pmc(cc[0 + ido * (2 + k * cdim)], cc[0 + ido * (5 + k * cdim)]);
-            let (t4, t5) = pmc(cc[0 + ido * (3 + k * cdim)], cc[0 + ido * (4 + k * cdim)]);
-
-            ch[0 + ido * (k + l1 * 0)] = t1 + t2 + t3 + t4;
-
-            let ca = Complex::new(
-                t1.re + TW1R * t2.re + TW2R * t3.re + TW3R * t4.re,
-                t1.im + TW1R * t2.im + TW2R * t3.im + TW3R * t4.im
-            );
-            let cb = Complex::new(
-                -(tw1i * t7.im + tw2i * t6.im + tw3i * t5.im),
-                tw1i * t7.re + tw2i * t6.re + tw3i * t5.re
-            );
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 6)]) = pmc(ca, cb);
-
-            let ca = Complex::new(
-                t1.re + TW2R * t2.re + TW3R * t3.re + TW1R * t4.re,
-                t1.im + TW2R * t2.im + TW3R * t3.im + TW1R * t4.im
-            );
-            let cb = Complex::new(
-                -(tw2i * t7.im - tw3i * t6.im - tw1i * t5.im),
-                tw2i * t7.re - tw3i * t6.re - tw1i * t5.re
-            );
-            (ch[0 + ido * (k + l1 * 2)], ch[0 + ido * (k + l1 * 5)]) = pmc(ca, cb);
-
-            let ca = Complex::new(
-                t1.re + TW3R * t2.re + TW1R * t3.re + TW2R * t4.re,
-                t1.im + TW3R * t2.im + TW1R * t3.im + TW2R * t4.im
-            );
-            let cb = Complex::new(
-                -(tw3i * t7.im - tw1i * t6.im + tw2i * t5.im),
-                tw3i * t7.re - tw1i * t6.re + tw2i * t5.re
-            );
-            (ch[0 + ido * (k + l1 * 3)], ch[0 + ido * (k + l1 * 4)]) = pmc(ca, cb);
+        for k in start..end {
+            let y = bfly.apply(core::array::from_fn(|j| cc.at(k * R + j) * fct));
+            for j in 0..R { rows[j].put(k - start, y[j]); }
         }
+        return;
     }
-    else {
-        for k in 0..l1 {
-            let t1 = cc[0 + ido * (0 + k * cdim)];
-            let (t2, t7) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (6 + k * cdim)]);
-            let (t3, t6) = pmc(cc[0 + ido * (2 + k * cdim)], cc[0 + ido * (5 + k * cdim)]);
-            let (t4, t5) = pmc(cc[0 + ido * (3 + k * cdim)], cc[0 + ido * (4 + k * cdim)]);
 
-            ch[0 + ido * (k + l1 * 0)] = t1 + t2 + t3 + t4;
+    let mut m = start;
+    while m < end {
+        let (k, mut i) = (m / ido, m % ido);
+        let stop = ido.min(end - k * ido);
+        let at = |i: usize| k * ido + i - start;
 
-            let ca = Complex::new(
-                t1.re + TW1R * t2.re + TW2R * t3.re + TW3R * t4.re,
-                t1.im + TW1R * t2.im + TW2R * t3.im + TW3R * t4.im
-            );
-            let cb = Complex::new(
-                -(tw1i * t7.im + tw2i * t6.im + tw3i * t5.im),
-                tw1i * t7.re + tw2i * t6.re + tw3i * t5.re
-            );
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 6)]) = pmc(ca, cb);
-
-            let ca = Complex::new(
-                t1.re + TW2R * t2.re + TW3R * t3.re + TW1R * t4.re,
-                t1.im + TW2R * t2.im + TW3R * t3.im + TW1R * t4.im
-            );
-            let cb = Complex::new(
-                -(tw2i * t7.im - tw3i * t6.im - tw1i * t5.im),
-                tw2i * t7.re - tw3i * t6.re - tw1i * t5.re
-            );
-            (ch[0 + ido * (k + l1 * 2)], ch[0 + ido * (k + l1 * 5)]) = pmc(ca, cb);
+        if i == 0 {
+            let y = bfly.apply(core::array::from_fn(|j| cc.at(ido * (j + k * R))));
+            for j in 0..R { rows[j].put(at(0), y[j]); }
+            i = 1;
+        }
 
-            let ca = Complex::new(
-                t1.re + TW3R * t2.re + TW1R * t3.re + TW2R * t4.re,
-                t1.im + TW3R * t2.im + TW1R * t3.im + TW2R * t4.im
-            );
-            let cb = Complex::new(
-                -(tw3i * t7.im - tw1i * t6.im + tw2i * t5.im),
-                tw3i * t7.re - tw1i * t6.re + tw2i * t5.re
-            );
-            (ch[0 + ido * (k + l1 * 3)], ch[0 + ido * (k + l1 * 4)]) = pmc(ca, cb);
-
-            for i in 1..ido {
-                let t1 = cc[i + ido * (0 + k * cdim)];
-                let (t2, t7) = pmc(cc[i + ido * (1 + k * cdim)], cc[i + ido * (6 + k * cdim)]);
-                let (t3, t6) = pmc(cc[i + ido * (2 + k * cdim)], cc[i + ido * (5 + k * cdim)]);
-                let (t4, t5) = pmc(cc[i + ido * (3 + k * cdim)], cc[i + ido * (4 + k * cdim)]);
-
-                ch[i + ido * (k + l1 * 0)] = t1 + t2 + t3 + t4;
-
-                let ca = Complex::new(
-                    t1.re + TW1R * t2.re + TW2R * t3.re + TW3R * t4.re,
-                    t1.im + TW1R * t2.im + TW2R * t3.im + TW3R * t4.im
-                );
-                let cb = Complex::new(
-                    -(tw1i * t7.im + tw2i * t6.im + tw3i * t5.im),
-                    tw1i * t7.re + tw2i * t6.re + tw3i * t5.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 1)] = if sign < 0 { wa[i - 1 + 0 * (ido - 1)].conj() * da } else { wa[i - 1 + 0 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 6)] = if sign < 0 { wa[i - 1 + 5 * (ido - 1)].conj() * db } else { wa[i - 1 + 5 * (ido - 1)] * db };
-
-                let ca = Complex::new(
-                    t1.re + TW2R * t2.re + TW3R * t3.re + TW1R * t4.re,
-                    t1.im + TW2R * t2.im + TW3R * t3.im + TW1R * t4.im
-                );
-                let cb = Complex::new(
-                    -(tw2i * t7.im - tw3i * t6.im - tw1i * t5.im),
-                    tw2i * t7.re - tw3i * t6.re - tw1i * t5.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 2)] = if sign < 0 { wa[i - 1 + 1 * (ido - 1)].conj() * da } else { wa[i - 1 + 1 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 5)] = if sign < 0 { wa[i - 1 + 4 * (ido - 1)].conj() * db } else { wa[i - 1 + 4 * (ido - 1)] * db };
-
-                let ca = Complex::new(
-                    t1.re + TW3R * t2.re + TW1R * t3.re + TW2R * t4.re,
-                    t1.im + TW3R * t2.im + TW1R * t3.im + TW2R * t4.im
-                );
-                let cb = Complex::new(
-                    -(tw3i * t7.im - tw1i * t6.im + tw2i * t5.im),
-                    tw3i * t7.re - tw1i * t6.re + tw2i * t5.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 3)] = if sign < 0 { wa[i - 1 + 2 * (ido - 1)].conj() * da } else { wa[i - 1 + 2 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 4)] = if sign < 0 { wa[i - 1 + 3 * (ido - 1)].conj() * db } else { wa[i - 1 + 3 * (ido - 1)] * db };
+        // the baseline feature leaves everything to the single complexes, the loop the blocks
+        // replaced, so the bench has something to hold them against
+        while !cfg!(feature = "baseline") && i + LANES <= stop {
+            let y = bfly.apply(core::array::from_fn(|j| cc.block(i + ido * (j + k * R))));
+            rows[0].put_block(at(i), y[0]);
+            for j in 1..R {
+                let w = Block::load(&wa[i - 1 + (j - 1) * (ido - 1)..]);
+                rows[j].put_block(at(i), y[j].twiddle(w, sign));
             }
+            i += LANES;
         }
+
+        for i in i..stop {
+            let y = bfly.apply(core::array::from_fn(|j| cc.at(i + ido * (j + k * R))));
+            rows[0].put(at(i), y[0]);
+            for j in 1..R { rows[j].put(at(i), y[j].twiddle(wa[i - 1 + (j - 1) * (ido - 1)], sign)); }
+        }
+        m = k * ido + stop;
     }
 }
 
-fn pass11(ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8) {
-    let cdim = 11;
-    const TW1R: f64 = 0.8412535328311811688618;
-    const TW2R: f64 = 0.4154150130018864255293;
-    const TW3R: f64 = -0.1423148382732851404438;
-    const TW4R: f64 = -0.6548607339452850640569;
-    const TW5R: f64 = -0.9594929736144973898904;
-    let tw1i = (sign as f64) * 0.5406408174555975821076;
-    let tw2i = (sign as f64) * 0.9096319953545183714117;
-    let tw3i = (sign as f64) * 0.9898214418809327323761;
-    let tw4i = (sign as f64) * 0.755749574354258283774;
-    let tw5i = (sign as f64) * 0.2817325568414296977114;
-
-    if ido == 1 {
-        for k in 0..l1 {
-            let t1 = cc[0 + ido * (0 + k * cdim)];
-            let (t2, t11) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (10 + k * cdim)]);
-            let (t3, t10) = pmc(cc[0 + ido * (2 + k * cdim)], cc[0 + ido * (9 + k * cdim)]);
-            let (t4, t9) = pmc(cc[0 + ido * (3 + k * cdim)], cc[0 + ido * (8 + k * cdim)]);
-            let (t5, t8) = pmc(cc[0 + ido * (4 + k * cdim)], cc[0 + ido * (7 + k * cdim)]);
-            let (t6, t7) = pmc(cc[0 + ido * (5 + k * cdim)], cc[0 + ido * (6 + k * cdim)]);
-
-            ch[0 + ido * (k + l1 * 0)] = t1 + t2 + t3 + t4 + t5 + t6;
-
-            let ca = Complex::new(
-                t1.re + TW1R * t2.re + TW2R * t3.re + TW3R * t4.re + TW4R * t5.re + TW5R * t6.re,
-                t1.im + TW1R * t2.im + TW2R * t3.im + TW3R * t4.im + TW4R * t5.im + TW5R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw1i * t11.im + tw2i * t10.im + tw3i * t9.im + tw4i * t8.im + tw5i * t7.im),
-                tw1i * t11.re + tw2i * t10.re + tw3i * t9.re + tw4i * t8.re + tw5i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 10)]) = pmc(ca, cb);
-
-            let ca = Complex::new(
-                t1.re + TW2R * t2.re + TW4R * t3.re + TW5R * t4.re + TW3R * t5.re + TW1R * t6.re,
-                t1.im + TW2R * t2.im + TW4R * t3.im + TW5R * t4.im + TW3R * t5.im + TW1R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw2i * t11.im + tw4i * t10.im - tw5i * t9.im - tw3i * t8.im - tw1i * t7.im),
-                tw2i * t11.re + tw4i * t10.re - tw5i * t9.re - tw3i * t8.re - tw1i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 2)], ch[0 + ido * (k + l1 * 9)]) = pmc(ca, cb);
-
-            let ca = Complex::new(
-                t1.re + TW3R * t2.re + TW5R * t3.re + TW2R * t4.re + TW1R * t5.re + TW4R * t6.re,
-                t1.im + TW3R * t2.im + TW5R * t3.im + TW2R * t4.im + TW1R * t5.im + TW4R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw3i * t11.im - tw5i * t10.im - tw2i * t9.im + tw1i * t8.im + tw4i * t7.im),
-                tw3i * t11.re - tw5i * t10.re - tw2i * t9.re + tw1i * t8.re + tw4i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 3)], ch[0 + ido * (k + l1 * 8)]) = pmc(ca, cb);
+// radices with their own pass, everything else goes through passg
+fn fixed(ip: usize) -> bool { matches!(ip, 2 | 3 | 4 | 5 | 7 | 11) }
+
+// the pass for a fixed radix ip, false for anything else
+#[allow(clippy::too_many_arguments)]
+fn fixed_pass<T: Float>(ip: usize, ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T) -> bool {
+    match ip {
+        4 => pass4(ido, l1, cc, ch, wa, sign, fct),
+        2 => pass2(ido, l1, cc, ch, wa, sign, fct),
+        3 => pass3(ido, l1, cc, ch, wa, sign, fct),
+        5 => pass5(ido, l1, cc, ch, wa, sign, fct),
+        7 => pass7(ido, l1, cc, ch, wa, sign, fct),
+        11 => pass11(ido, l1, cc, ch, wa, sign, fct),
+        _ => return false
+    }
+    return true;
+}
 
-            let ca = Complex::new(
-                t1.re + TW4R * t2.re + TW3R * t3.re + TW1R * t4.re + TW5R * t5.re + TW2R * t6.re,
-                t1.im + TW4R * t2.im + TW3R * t3.im + TW1R * t4.im + TW5R * t5.im + TW2R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw4i * t11.im - tw3i * t10.im + tw1i * t9.im + tw5i * t8.im - tw2i * t7.im),
-                tw4i * t11.re - tw3i * t10.re + tw1i * t9.re + tw5i * t8.re - tw2i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 4)], ch[0 + ido * (k + l1 * 7)]) = pmc(ca, cb);
+fn pass2<T: Float>(ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T) {
+    pass::<T, 2>(ido, l1, cc, ch, wa, sign, fct, &Radix::new([], [], sign));
+}
 
-            let ca = Complex::new(
-                t1.re + TW5R * t2.re + TW1R * t3.re + TW4R * t4.re + TW2R * t5.re + TW3R * t6.re,
-                t1.im + TW5R * t2.im + TW1R * t3.im + TW4R * t4.im + TW2R * t5.im + TW3R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw5i * t11.im - tw1i * t10.im + tw4i * t9.im - tw2i * t8.im + tw3i * t7.im),
-                tw5i * t11.re - tw1i * t10.re + tw4i * t9.re - tw2i * t8.re + tw3i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 5)], ch[0 + ido * (k + l1 * 6)]) = pmc(ca, cb);
-        }
-    }
-    else {
-        for k in 0..l1 {
-            let t1 = cc[0 + ido * (0 + k * cdim)];
-            let (t2, t11) = pmc(cc[0 + ido * (1 + k * cdim)], cc[0 + ido * (10 + k * cdim)]);
-            let (t3, t10) = pmc(cc[0 + ido * (2 + k * cdim)], cc[0 + ido * (9 + k * cdim)]);
-            let (t4, t9) = pmc(cc[0 + ido * (3 + k * cdim)], cc[0 + ido * (8 + k * cdim)]);
-            let (t5, t8) = pmc(cc[0 + ido * (4 + k * cdim)], cc[0 + ido * (7 + k * cdim)]);
-            let (t6, t7) = pmc(cc[0 + ido * (5 + k * cdim)], cc[0 + ido * (6 + k * cdim)]);
-
-            ch[0 + ido * (k + l1 * 0)] = t1 + t2 + t3 + t4 + t5 + t6;
-
-            let ca = Complex::new( 
-                t1.re + TW1R * t2.re + TW2R * t3.re + TW3R * t4.re + TW4R * t5.re + TW5R * t6.re,
-                t1.im + TW1R * t2.im + TW2R * t3.im + TW3R * t4.im + TW4R * t5.im + TW5R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw1i * t11.im + tw2i * t10.im + tw3i * t9.im + tw4i * t8.im + tw5i * t7.im),
-                tw1i * t11.re + tw2i * t10.re + tw3i * t9.re + tw4i * t8.re + tw5i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 1)], ch[0 + ido * (k + l1 * 10)]) = pmc(ca, cb);
+#[allow(clippy::excessive_precision)]
+fn pass3<T: Float>(ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T) {
+    pass::<T, 3>(ido, l1, cc, ch, wa, sign, fct, &Radix::new([-0.5], [0.86602540378443864676], sign));
+}
 
-            let ca = Complex::new(
-                t1.re + TW2R * t2.re + TW4R * t3.re + TW5R * t4.re + TW3R * t5.re + TW1R * t6.re,
-                t1.im + TW2R * t2.im + TW4R * t3.im + TW5R * t4.im + TW3R * t5.im + TW1R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw2i * t11.im + tw4i * t10.im - tw5i * t9.im - tw3i * t8.im - tw1i * t7.im),
-                tw2i * t11.re + tw4i * t10.re - tw5i * t9.re - tw3i * t8.re - tw1i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 2)], ch[0 + ido * (k + l1 * 9)]) = pmc(ca, cb);
+fn pass4<T: Float>(ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T) {
+    pass::<T, 4>(ido, l1, cc, ch, wa, sign, fct, &Radix::new([], [], sign));
+}
 
-            let ca = Complex::new(
-                t1.re + TW3R * t2.re + TW5R * t3.re + TW2R * t4.re + TW1R * t5.re + TW4R * t6.re,
-                t1.im + TW3R * t2.im + TW5R * t3.im + TW2R * t4.im + TW1R * t5.im + TW4R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw3i * t11.im - tw5i * t10.im - tw2i * t9.im + tw1i * t8.im + tw4i * t7.im),
-                tw3i * t11.re - tw5i * t10.re - tw2i * t9.re + tw1i * t8.re + tw4i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 3)], ch[0 + ido * (k + l1 * 8)]) = pmc(ca, cb);
+#[allow(clippy::excessive_precision)]
+fn pass5<T: Float>(ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T) {
+    let cos = [0.3090169943749474241, -0.8090169943749474241];
+    let sin = [0.95105651629515357212, 0.58778525229247312917];
+    pass::<T, 5>(ido, l1, cc, ch, wa, sign, fct, &Radix::new(cos, sin, sign));
+}
 
-            let ca = Complex::new(
-                t1.re + TW4R * t2.re + TW3R * t3.re + TW1R * t4.re + TW5R * t5.re + TW2R * t6.re,
-                t1.im + TW4R * t2.im + TW3R * t3.im + TW1R * t4.im + TW5R * t5.im + TW2R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw4i * t11.im - tw3i * t10.im + tw1i * t9.im + tw5i * t8.im - tw2i * t7.im),
-                tw4i * t11.re - tw3i * t10.re + tw1i * t9.re + tw5i * t8.re - tw2i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 4)], ch[0 + ido * (k + l1 * 7)]) = pmc(ca, cb);
+#[allow(clippy::excessive_precision)]
+fn pass7<T: Float>(ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T) {
+    let cos = [0.623489801858733530525, -0.222520933956314404289, -0.9009688679024191262361];
+    let sin = [0.7818314824680298087084, 0.9749279121818236070181, 0.4338837391175581204758];
+    pass::<T, 7>(ido, l1, cc, ch, wa, sign, fct, &Radix::new(cos, sin, sign));
+}
 
-            let ca = Complex::new(
-                t1.re + TW5R * t2.re + TW1R * t3.re + TW4R * t4.re + TW2R * t5.re + TW3R * t6.re,
-                t1.im + TW5R * t2.im + TW1R * t3.im + TW4R * t4.im + TW2R * t5.im + TW3R * t6.im
-            );
-            let cb = Complex::new(
-                -(tw5i * t11.im - tw1i * t10.im + tw4i * t9.im - tw2i * t8.im + tw3i * t7.im),
-                tw5i * t11.re - tw1i * t10.re + tw4i * t9.re - tw2i * t8.re + tw3i * t7.re
-            );
-            (ch[0 + ido * (k + l1 * 5)], ch[0 + ido * (k + l1 * 6)]) = pmc(ca, cb);
-
-            for i in 1..ido {
-                let t1 = cc[i + ido * (0 + k * cdim)];
-                let (t2, t11) = pmc(cc[i + ido * (1 + k * cdim)], cc[i + ido * (10 + k * cdim)]);
-                let (t3, t10) = pmc(cc[i + ido * (2 + k * cdim)], cc[i + ido * (9 + k * cdim)]);
-                let (t4, t9) = pmc(cc[i + ido * (3 + k * cdim)], cc[i + ido * (8 + k * cdim)]);
-                let (t5, t8) = pmc(cc[i + ido * (4 + k * cdim)], cc[i + ido * (7 + k * cdim)]);
-                let (t6, t7) = pmc(cc[i + ido * (5 + k * cdim)], cc[i + ido * (6 + k * cdim)]);
-
-                ch[i + ido * (k + l1 * 0)] = t1 + t2 + t3 + t4 + t5 + t6;
-
-                let ca = Complex::new(
-                    t1.re + TW1R * t2.re + TW2R * t3.re + TW3R * t4.re + TW4R * t5.re + TW5R * t6.re,
-                    t1.im + TW1R * t2.im + TW2R * t3.im + TW3R * t4.im + TW4R * t5.im + TW5R * t6.im
-                );
-                let cb = Complex::new(
-                    -(tw1i * t11.im + tw2i * t10.im + tw3i * t9.im + tw4i * t8.im + tw5i * t7.im),
-                    tw1i * t11.re + tw2i * t10.re + tw3i * t9.re + tw4i * t8.re + tw5i * t7.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 1)] = if sign < 0 { wa[i - 1 + 0 * (ido - 1)].conj() * da } else { wa[i - 1 + 0 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 10)] = if sign < 0 { wa[i - 1 + 9 * (ido - 1)].conj() * db } else { wa[i - 1 + 9 * (ido - 1)] * db };
-
-                let ca = Complex::new(
-                    t1.re + TW2R * t2.re + TW4R * t3.re + TW5R * t4.re + TW3R * t5.re + TW1R * t6.re,
-                    t1.im + TW2R * t2.im + TW4R * t3.im + TW5R * t4.im + TW3R * t5.im + TW1R * t6.im
-                );
-                let cb = Complex::new(
-                    -(tw2i * t11.im + tw4i * t10.im - tw5i * t9.im - tw3i * t8.im - tw1i * t7.im),
-                    tw2i * t11.re + tw4i * t10.re - tw5i * t9.re - tw3i * t8.re - tw1i * t7.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 2)] = if sign < 0 { wa[i - 1 + 1 * (ido - 1)].conj() * da } else { wa[i - 1 + 1 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 9)] = if sign < 0 { wa[i - 1 + 8 * (ido - 1)].conj() * db } else { wa[i - 1 + 8 * (ido - 1)] * db };
-
-                let ca = Complex::new(
-                    t1.re + TW3R * t2.re + TW5R * t3.re + TW2R * t4.re + TW1R * t5.re + TW4R * t6.re,
-                    t1.im + TW3R * t2.im + TW5R * t3.im + TW2R * t4.im + TW1R * t5.im + TW4R * t6.im
-                );
-                let cb = Complex::new(
-                    -(tw3i * t11.im - tw5i * t10.im - tw2i * t9.im + tw1i * t8.im + tw4i * t7.im),
-                    tw3i * t11.re - tw5i * t10.re - tw2i * t9.re + tw1i * t8.re + tw4i * t7.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 3)] = if sign < 0 { wa[i - 1 + 2 * (ido - 1)].conj() * da } else { wa[i - 1 + 2 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 8)] = if sign < 0 { wa[i - 1 + 7 * (ido - 1)].conj() * db } else { wa[i - 1 + 7 * (ido - 1)] * db };
-
-                let ca = Complex::new(
-                    t1.re + TW4R * t2.re + TW3R * t3.re + TW1R * t4.re + TW5R * t5.re + TW2R * t6.re,
-                    t1.im + TW4R * t2.im + TW3R * t3.im + TW1R * t4.im + TW5R * t5.im + TW2R * t6.im
-                );
-                let cb = Complex::new(
-                    -(tw4i * t11.im - tw3i * t10.im + tw1i * t9.im + tw5i * t8.im - tw2i * t7.im),
-                    tw4i * t11.re - tw3i * t10.re + tw1i * t9.re + tw5i * t8.re - tw2i * t7.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 4)] = if sign < 0 { wa[i - 1 + 3 * (ido - 1)].conj() * da } else { wa[i - 1 + 3 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 7)] = if sign < 0 { wa[i - 1 + 6 * (ido - 1)].conj() * db } else { wa[i - 1 + 6 * (ido - 1)] * db };
-
-                let ca = Complex::new(
-                    t1.re + TW5R * t2.re + TW1R * t3.re + TW4R * t4.re + TW2R * t5.re + TW3R * t6.re,
-                    t1.im + TW5R * t2.im + TW1R * t3.im + TW4R * t4.im + TW2R * t5.im + TW3R * t6.im
-                );
-                let cb = Complex::new(
-                    -(tw5i * t11.im - tw1i * t10.im + tw4i * t9.im - tw2i * t8.im + tw3i * t7.im),
-                    tw5i * t11.re - tw1i * t10.re + tw4i * t9.re - tw2i * t8.re + tw3i * t7.re
-                );
-                let (da, db) = pmc(ca, cb);
-                ch[i + ido * (k + l1 * 5)] = if sign < 0 { wa[i - 1 + 4 * (ido - 1)].conj() * da } else { wa[i - 1 + 4 * (ido - 1)] * da };
-                ch[i + ido * (k + l1 * 6)] = if sign < 0 { wa[i - 1 + 5 * (ido - 1)].conj() * db } else { wa[i - 1 + 5 * (ido - 1)] * db };
-            }
-        }
-    }
+#[allow(clippy::excessive_precision)]
+fn pass11<T: Float>(ido: usize, l1: usize, cc: impl Src<T>, ch: impl Dst<T>, wa: &[Complex<T>], sign: i8, fct: T) {
+    let cos = [
+        0.8412535328311811688618, 0.4154150130018864255293, -0.1423148382732851404438,
+        -0.6548607339452850640569, -0.9594929736144973898904
+    ];
+    let sin = [
+        0.5406408174555975821076, 0.9096319953545183714117, 0.9898214418809327323761,
+        0.755749574354258283774, 0.2817325568414296977114
+    ];
+    pass::<T, 11>(ido, l1, cc, ch, wa, sign, fct, &Radix::new(cos, sin, sign));
 }
 
-fn passg(ido: usize, ip: usize, l1: usize, cc: &mut [Complex], ch: &mut [Complex], wa: &[Complex], csarr: &[Complex], sign: i8) {
+#[allow(clippy::too_many_arguments, clippy::identity_op, clippy::erasing_op)]
+fn passg<T: Float>(ido: usize, ip: usize, l1: usize, cc: &mut [Complex<T>], ch: &mut [Complex<T>], wa: &[Complex<T>], csarr: &[Complex<T>], sign: i8, fct: T) {
     let cdim = ip;
     let ipph = (ip + 1) >> 1;
     let idl1 = ido * l1;
 
-    let mut wal = alloc::vec![Complex::new(0.0, 0.0); ip];
-    wal[0] = Complex::new(1.0, 0.0);
-    for i in 1..ip { wal[i] = Complex::new(csarr[i].re, sign as f64 * csarr[i].im); }
+    let wal = |x: usize| if sign > 0 { csarr[x] } else { csarr[x].conj() };
 
     for k in 0..l1 {
         for i in 0..ido {
-            ch[i + ido * (k + l1 * 0)] = cc[i + ido * (0 + k * cdim)];
+            ch[i + ido * (k + l1 * 0)] = cc[i + ido * (0 + k * cdim)] * fct;
         }
     }
 
@@ -592,7 +425,7 @@ fn passg(ido: usize, ip: usize, l1: usize, cc: &mut [Complex], ch: &mut [Complex
         let jc = ip - j;
         for k in 0..l1 {
             for i in 0..ido {
-                (ch[i + ido * (k + l1 * j)], ch[i + ido * (k + l1 * jc)]) = pmc(cc[i + ido * (j + k * cdim)], cc[i + ido * (jc + k * cdim)]);
+                (ch[i + ido * (k + l1 * j)], ch[i + ido * (k + l1 * jc)]) = pmc(cc[i + ido * (j + k * cdim)] * fct, cc[i + ido * (jc + k * cdim)] * fct);
             }
         }
     }
@@ -610,14 +443,15 @@ fn passg(ido: usize, ip: usize, l1: usize, cc: &mut [Complex], ch: &mut [Complex
     for l in 1..ipph {
         let lc = ip - l;
 
+        let (wal1, wal2) = (wal(l), wal(2 * l));
         for ik in 0..idl1 {
             cc[ik + idl1 * l] = Complex::new(
-                ch[ik + idl1 * 0].re + wal[l].re * ch[ik + idl1 * 1].re + wal[2 * l].re * ch[ik + idl1 * 2].re,
-                ch[ik + idl1 * 0].im + wal[l].re * ch[ik + idl1 * 1].im + wal[2 * l].re * ch[ik + idl1 * 2].im
+                ch[ik + idl1 * 0].re + wal1.re * ch[ik + idl1 * 1].re + wal2.re * ch[ik + idl1 * 2].re,
+                ch[ik + idl1 * 0].im + wal1.re * ch[ik + idl1 * 1].im + wal2.re * ch[ik + idl1 * 2].im
             );
             cc[ik + idl1 * lc] = Complex::new(
-                -wal[l].im * ch[ik + idl1 * (ip - 1)].im - wal[2 * l].im * ch[ik + idl1 * (ip - 2)].im,
-                wal[l].im * ch[ik + idl1 * (ip - 1)].re + wal[2 * l].im * ch[ik + idl1 * (ip - 2)].re
+                -wal1.im * ch[ik + idl1 * (ip - 1)].im - wal2.im * ch[ik + idl1 * (ip - 2)].im,
+                wal1.im * ch[ik + idl1 * (ip - 1)].re + wal2.im * ch[ik + idl1 * (ip - 2)].re
             );
         }
 
@@ -627,12 +461,12 @@ fn passg(ido: usize, ip: usize, l1: usize, cc: &mut [Complex], ch: &mut [Complex
 
         while j < ipph - 1 {
             iwal += l;
-            if iwal > ip { iwal -= ip; }
-            let xwal = wal[iwal];
+            if iwal >= ip { iwal -= ip; }
+            let xwal = wal(iwal);
 
             iwal += l;
-            if iwal > ip { iwal -= ip; }
-            let xwal2 = wal[iwal];
+            if iwal >= ip { iwal -= ip; }
+            let xwal2 = wal(iwal);
 
             for ik in 0..idl1 {
                 cc[ik + idl1 * l].re += ch[ik + idl1 * j].re * xwal.re + ch[ik + idl1 * (j + 1)].re * xwal2.re;
@@ -646,8 +480,8 @@ fn passg(ido: usize, ip: usize, l1: usize, cc: &mut [Complex], ch: &mut [Complex
 
         while j < ipph {
             iwal += l;
-            if iwal > ip { iwal -= ip; }
-            let xwal = wal[iwal];
+            if iwal >= ip { iwal -= ip; }
+            let xwal = wal(iwal);
 
             for ik in 0..idl1 {
                 cc[ik + idl1 * l].re += ch[ik + idl1 * j].re * xwal.re;
@@ -691,60 +525,85 @@ fn passg(ido: usize, ip: usize, l1: usize, cc: &mut [Complex], ch: &mut [Complex
 }
 
 #[derive(Clone)]
-pub struct FactorData {
+pub struct FactorData<T> {
     pub fct: usize,
-    pub tw: Vec<Complex>,
-    pub tws: Vec<Complex>
+    pub tw: Vec<Complex<T>>,
+    pub tws: Vec<Complex<T>>
+}
+
+// bytes held in the per-pass twiddle tables
+pub fn table_bytes<T>(fct: &[FactorData<T>]) -> usize {
+    return fct.iter().map(|f| f.tw.len() + f.tws.len()).sum::<usize>() * core::mem::size_of::<Complex<T>>();
 }
 
-pub struct CooleyTukey {
+#[allow(clippy::manual_is_multiple_of)]
+pub fn factorize<T>(mut len: usize) -> Vec<FactorData<T>> {
+    let mut fct = Vec::new();
+
+    while len & 3 == 0 {
+        fct.push(FactorData { fct: 4, tw: Vec::new(), tws: Vec::new() });
+        len >>= 2;
+    }
+
+    if len & 1 == 0 {
+        len >>= 1;
+        fct.push(FactorData { fct: 2, tw: Vec::new(), tws: Vec::new() });
+        let fctlen = fct.len(); fct.swap(0, fctlen - 1);
+    }
+
+    let mut maxl = libm::sqrt(len as f64) as usize + 1;
+    let mut divisor = 3;
+    while len > 1 && divisor < maxl {
+        if len % divisor == 0 {
+            while len % divisor == 0 {
+                fct.push(FactorData { fct: divisor, tw: Vec::new(), tws: Vec::new() });
+                len /= divisor;
+            }
+            maxl = libm::sqrt(len as f64) as usize + 1;
+        }
+        divisor += 2;
+    }
+
+    if len > 1 { fct.push(FactorData { fct: len, tw: Vec::new(), tws: Vec::new() }); }
+    return fct;
+}
+
+// the general pass pairs up j and ip - j, so even radices have to be one of the fixed 2 and 4
+pub fn check_factors(len: usize, factors: &[usize]) -> Result {
+    let product = factors.iter().try_fold(1usize, |p, &f| if f < 2 || (f % 2 == 0 && f > 4) { None } else { p.checked_mul(f) });
+    if product != Some(len.max(1)) { return Err(FftError::InvalidParameter("factors must be 2, 4 or odd and multiply to the length")); }
+    return Ok(());
+}
+
+pub struct CooleyTukey<T = f64> {
     len: usize,
-    fct: Vec<FactorData>
+    fct: Vec<FactorData<T>>
 }
 
-impl CooleyTukey {
-    pub fn new(len: usize) -> CooleyTukey {
+impl<T: Float> CooleyTukey<T> {
+    pub fn new(len: usize) -> CooleyTukey<T> {
         let mut plan = CooleyTukey { len, fct: Vec::new() };
         if len < 2 { return plan; }
 
-        plan.factorize();
+        plan.fct = factorize(len);
         plan.compute_twiddle();
         return plan;
     }
 
-    fn factorize(&mut self) {
-        let mut len = self.len;
-
-        while len & 3 == 0 {
-            self.fct.push(FactorData { fct: 4, tw: Vec::new(), tws: Vec::new() });
-            len >>= 2;
-        }
-
-        if len & 1 == 0 {
-            len >>= 1;
-            self.fct.push(FactorData { fct: 2, tw: Vec::new(), tws: Vec::new() });
-            let fctlen = self.fct.len(); self.fct.swap(0, fctlen - 1);
-        }
-
-        let mut maxl = libm::sqrt(len as f64) as usize + 1;
-        let mut divisor = 3;
-        while len > 1 && divisor < maxl {
-            if len % divisor == 0 {
-                while len % divisor == 0 {
-                    self.fct.push(FactorData { fct: divisor, tw: Vec::new(), tws: Vec::new() });
-                    len /= divisor;
-                }
-                maxl = libm::sqrt(len as f64) as usize + 1;
-            }
-            divisor += 2;
-        }
+    // any order of radices, as long as they multiply to len
+    pub fn with_factors(len: usize, factors: &[usize]) -> Result<CooleyTukey<T>> {
+        check_factors(len, factors)?;
+        let mut plan = CooleyTukey { len, fct: Vec::new() };
+        if len < 2 { return Ok(plan); }
 
-        if len > 1 { self.fct.push(FactorData { fct: len, tw: Vec::new(), tws: Vec::new() }); }
+        plan.fct = factors.iter().map(|&f| FactorData { fct: f, tw: Vec::new(), tws: Vec::new() }).collect();
+        plan.compute_twiddle();
+        return Ok(plan);
     }
 
     fn compute_twiddle(&mut self) {
         let len = self.len;
-        let mut twid = alloc::vec![Complex::new(0.0, 0.0); len];
+        let mut twid = alloc::vec![Complex::new(T::ZERO, T::ZERO); len];
         sincos_2pibyn(len, &mut twid);
 
         let mut l1 = 1;
@@ -754,7 +613,7 @@ impl CooleyTukey {
             let ido = len / (l1 * ip);
 
             let tw_size = (ip - 1) * (ido - 1);
-            self.fct[k].tw = alloc::vec![Complex::new(0.0, 0.0); tw_size];
+            self.fct[k].tw = alloc::vec![Complex::new(T::ZERO, T::ZERO); tw_size];
 
             for j in 1..ip {
                 for i in 1..ido {
@@ -763,8 +622,8 @@ impl CooleyTukey {
                 }
             }
 
-            if ip > 11 {
-                self.fct[k].tws = alloc::vec![Complex::new(0.0, 0.0); ip];
+            if !fixed(ip) {
+                self.fct[k].tws = alloc::vec![Complex::new(T::ZERO, T::ZERO); ip];
                 for j in 0..ip { self.fct[k].tws[j] = twid[j * l1 * ido]; }
             }
 
@@ -772,38 +631,248 @@ impl CooleyTukey {
         }
     }
 
-    pub fn forward(&self, data: &mut [Complex], fct: f64) -> Result { return self.fft(data, fct, -1); }
-    pub fn backward(&self, data: &mut [Complex], fct: f64) -> Result { return self.fft(data, fct, 1); }
+    pub fn forward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, -1); }
+    pub fn backward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, 1); }
 
-    fn fft(&self, data: &mut [Complex], fct: f64, sign: i8) -> Result {
-        if self.len != data.len() { return Err(()); }
-        if self.len < 2 { return Ok(()); }
+    pub fn forward_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_with_scratch(data, scratch, fct, -1); }
+    pub fn backward_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_with_scratch(data, scratch, fct, 1); }
 
-        if fct != 1.0 { data.iter_mut().for_each(|d| *d *= fct); }
-        let mut l1 = 1;
-        let mut ch = alloc::vec![Complex::new(0.0, 0.0); data.len()];
-        let (mut p1, mut p2) = (&mut data[..], &mut ch[..]);
+    fn fft(&self, data: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+        return self.fft_with_scratch(data, &mut scratch, fct, sign);
+    }
 
-        for k1 in 0..self.fct.len() {
+    fn fft_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        check_len(self.len, data.len())?;
+        check_scratch(self.scratch_len(), scratch.len())?;
+        check_finite(data)?;
+        if self.len < 2 {
+            if fct != T::ONE { data.iter_mut().for_each(|d| *d *= fct); }
+            return Ok(());
+        }
+
+        let ch = &mut scratch[..self.len];
+        if !self.passes(0, data, ch, sign, fct) { data.copy_from_slice(ch); }
+        return Ok(());
+    }
+
+    pub fn forward_into(&self, input: &[Complex<T>], output: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_into(input, output, scratch, fct, -1); }
+    pub fn backward_into(&self, input: &[Complex<T>], output: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_into(input, output, scratch, fct, 1); }
+
+    // every fixed radix pass moves the data over to the other buffer and the general one leaves it
+    // where it was, so counting them says where to start for the last pass to land in output.
+    // the first pass reads straight from input unless it's a general one, which works in place
+    fn fft_into(&self, input: &[Complex<T>], output: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        check_len(self.len, input.len())?;
+        check_len(self.len, output.len())?;
+        check_scratch(self.scratch_len(), scratch.len())?;
+        check_finite(input)?;
+        if self.len < 2 {
+            for (o, &x) in output.iter_mut().zip(input) { *o = if fct != T::ONE { x * fct } else { x }; }
+            return Ok(());
+        }
+
+        let ch = &mut scratch[..self.len];
+        let moves = self.fct.iter().filter(|f| fixed(f.fct)).count();
+        if !fixed(self.fct[0].fct) {
+            let (p1, p2) = if moves % 2 == 0 { (output, ch) } else { (ch, output) };
+            p1.copy_from_slice(input);
+            self.passes(0, p1, p2, sign, fct);
+            return Ok(());
+        }
+
+        let (p1, p2) = if moves % 2 == 1 { (output, ch) } else { (ch, output) };
+        let f = if self.fct.len() == 1 { fct } else { T::ONE };
+        self.pass(0, 1, input, p1, sign, f);
+        self.passes(1, p1, p2, sign, fct);
+        return Ok(());
+    }
+
+    // passes from k1 = start on, with the data in p1. true if it ends up back in p1
+    fn passes<'a>(&self, start: usize, mut p1: &'a mut [Complex<T>], mut p2: &'a mut [Complex<T>], sign: i8, fct: T) -> bool {
+        let mut l1: usize = self.fct[..start].iter().map(|f| f.fct).product();
+        let mut home = true;
+
+        for k1 in start..self.fct.len() {
             let ip = self.fct[k1].fct;
             let l2 = ip * l1;
             let ido = self.len / l2;
+            // the scale rides along with the loads of the last pass (the only one with ido == 1)
+            let f = if l2 == self.len { fct } else { T::ONE };
 
-            match ip {
-                4 => pass4(ido, l1, p1, p2, &self.fct[k1].tw, sign),
-                2 => pass2(ido, l1, p1, p2, &self.fct[k1].tw, sign),
-                3 => pass3(ido, l1, p1, p2, &self.fct[k1].tw, sign),
-                5 => pass5(ido, l1, p1, p2, &self.fct[k1].tw, sign),
-                7 => pass7(ido, l1, p1, p2, &self.fct[k1].tw, sign),
-                11 => pass11(ido, l1, p1, p2, &self.fct[k1].tw, sign),
-                _ => { passg(ido, ip, l1, p1, p2, &self.fct[k1].tw, &self.fct[k1].tws, sign); (p1, p2) = (p2, p1); }
-            }
+            if self.pass(k1, l1, p1, p2, sign, f) { (p1, p2, home) = (p2, p1, !home); }
+            else { passg(ido, ip, l1, p1, p2, &self.fct[k1].tw, &self.fct[k1].tws, sign, f); }
+            l1 = l2;
+        }
+        return home;
+    }
+
+    // the fixed radix pass k1 from cc into ch, false for a radix only passg does
+    fn pass(&self, k1: usize, l1: usize, cc: &[Complex<T>], ch: &mut [Complex<T>], sign: i8, f: T) -> bool {
+        let ip = self.fct[k1].fct;
+        let ido = self.len / (ip * l1);
+        let wa = &self.fct[k1].tw;
+
+        #[cfg(feature = "simd")]
+        if T::simd_pass(ip, ido, l1, cc, ch, wa, sign, f) { return true; }
+
+        return fixed_pass(ip, ido, l1, cc, ch, wa, sign, f);
+    }
+
+    pub fn forward_split(&self, re: &mut [T], im: &mut [T], fct: T) -> Result { return self.fft_split(re, im, fct, -1); }
+    pub fn backward_split(&self, re: &mut [T], im: &mut [T], fct: T) -> Result { return self.fft_split(re, im, fct, 1); }
+
+    pub fn forward_split_with_scratch(&self, re: &mut [T], im: &mut [T], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_split_with_scratch(re, im, scratch, fct, -1); }
+    pub fn backward_split_with_scratch(&self, re: &mut [T], im: &mut [T], scratch: &mut [Complex<T>], fct: T) -> Result { return self.fft_split_with_scratch(re, im, scratch, fct, 1); }
 
-            (p1, p2, l1) = (p2, p1, l2);
+    fn fft_split(&self, re: &mut [T], im: &mut [T], fct: T, sign: i8) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.split_scratch_len()];
+        return self.fft_split_with_scratch(re, im, &mut scratch, fct, sign);
+    }
+
+    // the same passes, ping-ponging between re and im and an interleaved scratch. the general
+    // radix only comes interleaved, so it packs the data into the scratch first if it isn't there
+    // already, and uses the second half of the scratch for its own
+    fn fft_split_with_scratch(&self, re: &mut [T], im: &mut [T], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        check_len(self.len, re.len())?;
+        check_len(self.len, im.len())?;
+        check_scratch(self.split_scratch_len(), scratch.len())?;
+        check_finite_real(re)?;
+        check_finite_real(im)?;
+        if self.len < 2 {
+            if fct != T::ONE { re.iter_mut().chain(im.iter_mut()).for_each(|d| *d *= fct); }
+            return Ok(());
+        }
+
+        let (ch, packed) = scratch.split_at_mut(self.len);
+        let (mut l1, mut home) = (1, true);
+        for k1 in 0..self.fct.len() {
+            let ip = self.fct[k1].fct;
+            let l2 = ip * l1;
+            let ido = self.len / l2;
+            let f = if l2 == self.len { fct } else { T::ONE };
+            let (tw, tws) = (&self.fct[k1].tw, &self.fct[k1].tws);
+
+            let moved = if home { fixed_pass(ip, ido, l1, (&*re, &*im), &mut *ch, tw, sign, f) }
+                else { fixed_pass(ip, ido, l1, &*ch, (&mut *re, &mut *im), tw, sign, f) };
+            if moved { home = !home; }
+            else {
+                if home { for (c, (&r, &i)) in ch.iter_mut().zip(re.iter().zip(im.iter())) { *c = Complex::new(r, i); } }
+                passg(ido, ip, l1, ch, packed, tw, tws, sign, f);
+                home = false;
+            }
+            l1 = l2;
+        }
+        if !home {
+            for (c, (r, i)) in ch.iter().zip(re.iter_mut().zip(im.iter_mut())) { (*r, *i) = (c.re, c.im); }
         }
-        if p1.as_ptr() != data.as_ptr() { data.copy_from_slice(&ch); }
         return Ok(());
     }
 
     pub fn len(&self) -> usize { self.len }
-}
\ No newline at end of file
+    pub fn factors(&self) -> Vec<usize> { self.fct.iter().map(|f| f.fct).collect() }
+
+    pub fn describe(&self) -> PlanInfo {
+        return PlanInfo {
+            algorithm: Algorithm::Ct, len: self.len, real: false, factors: self.factors(), padded_len: None,
+            twiddle_bytes: table_bytes(&self.fct), flops: cost_guess(self.len), children: Vec::new()
+        };
+    }
+
+    pub fn scratch_len(&self) -> usize { if self.len < 2 { 0 } else { self.len } }
+    // the general radix wants a second buffer next to the packed data
+    pub fn split_scratch_len(&self) -> usize {
+        if self.len < 2 { return 0; }
+        return if self.fct.iter().all(|f| fixed(f.fct)) { self.len } else { 2 * self.len };
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{complex_signal, dft, err};
+
+    fn same(a: Complex, b: Complex) -> bool { a.re.to_bits() == b.re.to_bits() && a.im.to_bits() == b.im.to_bits() }
+
+    // every lane of a block comes out with the bits of the same butterfly on a single complex
+    fn check_block<const R: usize, const H: usize>(sign: i8) where Radix<f64, H>: Butterfly<f64, R> {
+        let radix = Radix::<f64, H>::new(core::array::from_fn(|j| 0.1 + j as f64 * 0.3), core::array::from_fn(|j| 0.9 - j as f64 * 0.2), sign);
+        let x: [Vec<Complex>; R] = core::array::from_fn(|j| complex_signal(LANES, j as u64 + 1));
+        let w = complex_signal(LANES, 77);
+        let blocks = radix.apply(core::array::from_fn(|j| Block::load(&x[j])));
+        for l in 0..LANES {
+            let single = radix.apply(core::array::from_fn(|j| x[j][l]));
+            for j in 0..R {
+                assert!(same(Complex::new(blocks[j].re[l], blocks[j].im[l]), single[j]), "radix {} lane {} output {}", R, l, j);
+                let (tb, ts) = (blocks[j].twiddle(Block::load(&w), sign), Lane::twiddle(single[j], w[l], sign));
+                assert!(same(Complex::new(tb.re[l], tb.im[l]), ts), "radix {} lane {} output {} twiddled", R, l, j);
+            }
+        }
+    }
+
+    #[test]
+    fn blocks_match_single_complexes() {
+        for sign in [-1, 1] {
+            check_block::<2, 0>(sign);
+            check_block::<3, 1>(sign);
+            check_block::<4, 0>(sign);
+            check_block::<5, 2>(sign);
+            check_block::<7, 3>(sign);
+            check_block::<11, 5>(sign);
+        }
+    }
+
+    // each fixed radix with ido both above and below LANES, passg on 13 and 17, and mixes
+    #[test]
+    fn matches_dft() {
+        for n in [1, 2, 3, 5, 16, 27, 125, 343, 1331, 13 * 13, 17 * 8, 2 * 3 * 4 * 5 * 7, 11 * 13 * 4, 1024] {
+            let x = complex_signal(n, n as u64);
+            let plan = CooleyTukey::<f64>::new(n);
+            let want = dft(&x, -1);
+            let mut y = x.clone();
+            plan.forward(&mut y, 1.0).unwrap();
+            assert!(err(&y, &want) < 1e-13, "n = {}", n);
+
+            let (mut re, mut im): (Vec<f64>, Vec<f64>) = x.iter().map(|c| (c.re, c.im)).unzip();
+            plan.forward_split(&mut re, &mut im, 1.0).unwrap();
+            let split: Vec<Complex> = re.iter().zip(&im).map(|(&r, &i)| Complex::new(r, i)).collect();
+            assert!(err(&split, &want) < 1e-13, "n = {}", n);
+
+            plan.backward(&mut y, 1.0 / n as f64).unwrap();
+            assert!(err(&y, &x) < 1e-13, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn chosen_factors() {
+        let x = complex_signal(60, 3);
+        let want = dft(&x, -1);
+        for factors in [&[3, 4, 5][..], &[5, 2, 3, 2], &[15, 4]] {
+            let plan = CooleyTukey::<f64>::with_factors(60, factors).unwrap();
+            assert_eq!(plan.factors(), factors);
+            let mut y = x.clone();
+            plan.forward(&mut y, 1.0).unwrap();
+            assert!(err(&y, &want) < 1e-13, "{:?}", factors);
+        }
+        for factors in [&[6, 10][..], &[2, 2], &[1, 60], &[]] {
+            assert!(matches!(CooleyTukey::<f64>::with_factors(60, factors), Err(FftError::InvalidParameter(_))), "{:?}", factors);
+        }
+    }
+
+    // general radices stay put and fixed ones move, first or not, an odd or even number of them
+    #[test]
+    fn into_matches_in_place() {
+        for factors in [&[2][..], &[13], &[13, 4], &[4, 13], &[4, 2, 13], &[3, 5, 7], &[13, 17], &[17, 3, 13]] {
+            let n = factors.iter().product();
+            let plan = CooleyTukey::<f64>::with_factors(n, factors).unwrap();
+            let x = complex_signal(n, 4);
+            let mut scratch = alloc::vec![Complex::new(0.0, 0.0); plan.scratch_len()];
+            for (sign, fct) in [(-1, 1.0), (1, 0.5)] {
+                let mut want = x.clone();
+                plan.fft_with_scratch(&mut want, &mut scratch, fct, sign).unwrap();
+                let mut out = alloc::vec![Complex::new(9.0, 9.0); n];
+                plan.fft_into(&x, &mut out, &mut scratch, fct, sign).unwrap();
+                assert!(out.iter().zip(&want).all(|(a, b)| same(*a, *b)), "{:?} {}", factors, sign);
+            }
+        }
+    }
+}
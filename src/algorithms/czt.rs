@@ -0,0 +1,165 @@
+use crate::{
+    algorithms::cooleytukey::CooleyTukey,
+    error::{check_finite, check_len, check_scratch},
+    math::good_size, Complex, FftError, Float, Result
+};
+
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+// chirp z-transform, X[k] = sum x[n] A^-n W^nk for k in 0..points, i.e. the z-transform
+// sampled along the spiral z_k = A W^-k. same trick as bluestein, nk = (n^2 + k^2 - (k - n)^2) / 2
+// turns it into a convolution with the chirp W^(-m^2 / 2), only now input and output lengths
+// are independent. with |W| != 1 the chirp grows like |W|^(n^2 / 2), keep the spiral tame
+pub struct CztPlan<T = f64> {
+    n: usize,
+    m: usize,
+    plan: CooleyTukey<T>,
+    pre: Vec<Complex<T>>, // A^-n W^(n^2 / 2)
+    post: Vec<Complex<T>>, // W^(k^2 / 2)
+    kernel: Vec<Complex<T>> // transformed W^(-m^2 / 2), 1 / l folded in
+}
+
+// exp(-n ln A + n^2 / 2 ln W) in one go, so large powers don't overflow halfway
+fn chirp(la: (f64, f64), lw: (f64, f64), n: f64, sa: f64, sw: f64) -> Complex {
+    let (re, im) = (sa * n * la.0 + sw * 0.5 * n * n * lw.0, sa * n * la.1 + sw * 0.5 * n * n * lw.1);
+    let mag = libm::exp(re);
+    return Complex::new(mag * libm::cos(im), mag * libm::sin(im));
+}
+
+impl<T: Float> CztPlan<T> {
+    pub fn new(length: usize, points: usize, a: Complex, w: Complex) -> Result<Self> {
+        if length == 0 || points == 0 { return Err(FftError::UnsupportedSize(0)); }
+        let log = |z: Complex| (libm::log(libm::hypot(z.re, z.im)), libm::atan2(z.im, z.re));
+        let (la, lw) = (log(a), log(w));
+        if !(la.0.is_finite() && la.1.is_finite()) { return Err(FftError::InvalidParameter("A must be finite and nonzero")); }
+        if !(lw.0.is_finite() && lw.1.is_finite()) { return Err(FftError::InvalidParameter("W must be finite and nonzero")); }
+        return Ok(Self::from_logs(length, points, la, lw));
+    }
+
+    // dense spectrum of points bins over [f0, f1) in cycles per sample, spaced (f1 - f0) / points.
+    // zoom(n, n, 0.0, 1.0) is the plain dft. normalised frequencies, so divide hertz by the sample rate
+    pub fn zoom(length: usize, points: usize, f0: f64, f1: f64) -> Result<Self> {
+        if length == 0 || points == 0 { return Err(FftError::UnsupportedSize(0)); }
+        if !(f0.is_finite() && f1.is_finite()) { return Err(FftError::InvalidParameter("band edges must be finite")); }
+        let step = (f1 - f0) / points as f64;
+        return Ok(Self::from_logs(length, points, (0.0, 2.0 * PI * f0), (0.0, -2.0 * PI * step)));
+    }
+
+    fn from_logs(n: usize, m: usize, la: (f64, f64), lw: (f64, f64)) -> Self {
+        let l = good_size(n + m - 1);
+        let cvt = |z: Complex| Complex::new(T::from_f64(z.re), T::from_f64(z.im));
+
+        let pre = (0..n).map(|i| cvt(chirp(la, lw, i as f64, -1.0, 1.0))).collect();
+        let post = (0..m).map(|k| cvt(chirp(la, lw, k as f64, 0.0, 1.0))).collect();
+
+        // W^(-j^2 / 2) for lags j in -(n - 1)..m, negative lags wrapped to the end
+        let mut kernel = alloc::vec![Complex::new(T::ZERO, T::ZERO); l];
+        let xl = 1.0 / l as f64;
+        for (j, k) in kernel[..m].iter_mut().enumerate() { *k = cvt(chirp(la, lw, j as f64, 0.0, -1.0) * xl); }
+        for j in 1..n { kernel[l - j] = cvt(chirp(la, lw, j as f64, 0.0, -1.0) * xl); }
+
+        let plan = CooleyTukey::new(l);
+        plan.forward(&mut kernel, T::ONE).unwrap();
+        return CztPlan { n, m, plan, pre, post, kernel };
+    }
+
+    pub fn forward(&self, input: &[Complex<T>], output: &mut [Complex<T>]) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+        return self.forward_with_scratch(input, output, &mut scratch);
+    }
+
+    pub fn forward_with_scratch(&self, input: &[Complex<T>], output: &mut [Complex<T>], scratch: &mut [Complex<T>]) -> Result {
+        check_len(self.n, input.len())?;
+        check_len(self.m, output.len())?;
+        check_scratch(self.scratch_len(), scratch.len())?;
+        check_finite(input)?;
+        let (buf, scratch) = scratch.split_at_mut(self.plan.len());
+
+        for i in 0..self.n { buf[i] = input[i] * self.pre[i]; }
+        buf[self.n..].fill(Complex::new(T::ZERO, T::ZERO));
+
+        self.plan.forward_with_scratch(buf, scratch, T::ONE)?;
+        for (b, &k) in buf.iter_mut().zip(self.kernel.iter()) { *b *= k; }
+        self.plan.backward_with_scratch(buf, scratch, T::ONE)?;
+        for k in 0..self.m { output[k] = buf[k] * self.post[k]; }
+        return Ok(());
+    }
+
+    pub fn len(&self) -> usize { self.n }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+    pub fn points(&self) -> usize { self.m }
+    pub fn scratch_len(&self) -> usize { self.plan.len() + self.plan.scratch_len() }
+}
+
+// one-off zoom fft, builds a throwaway plan
+pub fn zoom_fft<T: Float>(input: &[Complex<T>], points: usize, f0: f64, f1: f64) -> Result<Vec<Complex<T>>> {
+    let plan = CztPlan::zoom(input.len(), points, f0, f1)?;
+    let mut output = alloc::vec![Complex::new(T::ZERO, T::ZERO); points];
+    plan.forward(input, &mut output)?;
+    return Ok(output);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{complex_signal, dft, err};
+
+    fn polar(r: f64, t: f64) -> Complex { Complex::new(r * libm::cos(t), r * libm::sin(t)) }
+
+    // X[k] = sum of x[n] A^-n W^nk, powers taken straight from the polar form
+    fn direct(x: &[Complex], m: usize, a: (f64, f64), w: (f64, f64)) -> Vec<Complex> {
+        return (0..m).map(|k| {
+            x.iter().enumerate().fold(Complex::new(0.0, 0.0), |acc, (n, &v)| {
+                let (n, k) = (n as f64, k as f64);
+                acc + v * polar(libm::pow(a.0, -n) * libm::pow(w.0, n * k), -n * a.1 + n * k * w.1)
+            })
+        }).collect();
+    }
+
+    #[test]
+    fn matches_direct_sum() {
+        let spirals = [(1.0, 0.0, 1.0, -0.1), (1.1, 0.3, 0.999, -2.0 * PI / 40.0), (0.9, -1.0, 1.002, 0.05)];
+        for (ar, at, wr, wt) in spirals {
+            for (n, m) in [(1, 1), (8, 8), (30, 7), (7, 45), (100, 64)] {
+                let x = complex_signal(n, (n * m) as u64);
+                let plan = CztPlan::<f64>::new(n, m, polar(ar, at), polar(wr, wt)).unwrap();
+                let mut y = alloc::vec![Complex::new(0.0, 0.0); m];
+                plan.forward(&x, &mut y).unwrap();
+                assert!(err(&y, &direct(&x, m, (ar, at), (wr, wt))) < 1e-12, "{} {} {} {}", n, m, ar, wr);
+            }
+        }
+    }
+
+    #[test]
+    fn zoom_over_the_whole_circle_is_the_dft() {
+        for n in [1, 5, 16, 97] {
+            let x = complex_signal(n, 3);
+            assert!(err(&zoom_fft(&x, n, 0.0, 1.0).unwrap(), &dft(&x, -1)) < 1e-12, "n = {}", n);
+        }
+    }
+
+    // bins at f0 + k (f1 - f0) / points of the dtft
+    #[test]
+    fn zoom_band() {
+        let x = complex_signal(200, 4);
+        let (f0, f1, points) = (0.1, 0.15, 50);
+        let want: Vec<Complex> = (0..points).map(|k| {
+            let f = f0 + k as f64 * (f1 - f0) / points as f64;
+            x.iter().enumerate().fold(Complex::new(0.0, 0.0), |acc, (n, &v)| acc + v * polar(1.0, -2.0 * PI * f * n as f64))
+        }).collect();
+        assert!(err(&zoom_fft(&x, points, f0, f1).unwrap(), &want) < 1e-12);
+    }
+
+    #[test]
+    fn bad_parameters() {
+        let one = Complex::new(1.0, 0.0);
+        assert!(matches!(CztPlan::<f64>::new(0, 4, one, one), Err(FftError::UnsupportedSize(0))));
+        assert!(matches!(CztPlan::<f64>::new(4, 4, Complex::new(0.0, 0.0), one), Err(FftError::InvalidParameter(_))));
+        assert!(matches!(CztPlan::<f64>::new(4, 4, one, Complex::new(f64::INFINITY, 0.0)), Err(FftError::InvalidParameter(_))));
+        assert!(matches!(CztPlan::<f64>::zoom(4, 4, 0.0, f64::NAN), Err(FftError::InvalidParameter(_))));
+        let plan = CztPlan::<f64>::zoom(4, 6, 0.0, 0.5).unwrap();
+        assert_eq!(plan.forward(&[one; 4], &mut [one; 5]), Err(FftError::LengthMismatch { expected: 6, actual: 5 }));
+    }
+}
@@ -0,0 +1,329 @@
+use crate::{
+    algorithms::{CfftPlan, RfftPlan},
+    error::check_len,
+    math::sincos_2pibyn, Complex, FftError, Float, Norm, Result
+};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DctType { I, II, III, IV }
+
+impl DctType {
+    fn inverse(self) -> Self {
+        match self {
+            Self::II => Self::III,
+            Self::III => Self::II,
+            kind => kind
+        }
+    }
+}
+
+enum Core<T> {
+    One(RfftPlan<T>), // real fft of the symmetric (or antisymmetric) extension
+    TwoThree(RfftPlan<T>, Vec<T>),
+    Four(CfftPlan<T>, Vec<Complex<T>>, Vec<Complex<T>>) // pre and post twiddles
+}
+
+struct R2r<T> {
+    len: usize,
+    kind: DctType,
+    cosine: bool,
+    core: Core<T>
+}
+
+impl<T: Float> R2r<T> {
+    fn new(len: usize, kind: DctType, cosine: bool) -> Self {
+        let core = match kind {
+            // nothing to transform, so no twiddles either
+            _ if len == 0 => Core::TwoThree(RfftPlan::new(0), Vec::new()),
+            DctType::I => {
+                let n = if cosine { 2 * len.saturating_sub(1) } else { 2 * (len + 1) };
+                Core::One(RfftPlan::new(n))
+            }
+            DctType::II | DctType::III => {
+                let mut tw = alloc::vec![Complex::new(T::ZERO, T::ZERO); 4 * len];
+                sincos_2pibyn(4 * len, &mut tw);
+                Core::TwoThree(RfftPlan::new(len), (0..len).map(|i| tw[i + 1].re).collect())
+            }
+            DctType::IV if len & 1 == 0 => {
+                // even lengths fold into a complex fft of half the size
+                let mut tw = alloc::vec![Complex::new(T::ZERO, T::ZERO); 16 * len];
+                sincos_2pibyn(16 * len, &mut tw);
+                let c2: Vec<Complex<T>> = (0..len / 2).map(|i| tw[8 * i + 1].conj()).collect();
+                Core::Four(CfftPlan::new(len / 2), c2.clone(), c2)
+            }
+            DctType::IV => {
+                // odd lengths go through a zero padded complex fft of twice the size
+                let mut tw = alloc::vec![Complex::new(T::ZERO, T::ZERO); 8 * len];
+                sincos_2pibyn(8 * len, &mut tw);
+                let pre = (0..len).map(|i| tw[2 * i].conj()).collect();
+                let post = (0..len).map(|i| tw[2 * i + 1].conj()).collect();
+                Core::Four(CfftPlan::new(2 * len), pre, post)
+            }
+        };
+        return R2r { len, kind, cosine, core };
+    }
+
+    // length of the equivalent real dft, which is what the normalisation is relative to
+    fn logical_len(&self) -> usize {
+        match (self.kind, self.cosine) {
+            (DctType::I, true) => 2 * self.len.saturating_sub(1),
+            (DctType::I, false) => 2 * (self.len + 1),
+            _ => 2 * self.len
+        }
+    }
+
+    fn fft(&self, data: &mut [T], norm: Norm, sign: i8) -> Result {
+        check_len(self.len, data.len())?;
+        if self.len == 0 { return Ok(()); }
+
+        let kind = if sign < 0 { self.kind } else { self.kind.inverse() };
+        let n = self.logical_len();
+        let fct = Norm::Custom(if sign < 0 { norm.forward_factor(n) } else { norm.backward_factor(n) });
+        let ortho = norm == Norm::Ortho;
+
+        match (&self.core, kind) {
+            (Core::One(plan), _) if self.cosine => self.dct1(plan, data, fct, ortho),
+            (Core::One(plan), _) => self.dst1(plan, data, fct),
+            (Core::TwoThree(plan, tw), DctType::II) => self.dcst2(plan, tw, data, fct, ortho),
+            (Core::TwoThree(plan, tw), _) => self.dcst3(plan, tw, data, fct, ortho),
+            (Core::Four(plan, pre, post), _) => self.dcst4(plan, pre, post, data, fct)
+        }
+    }
+
+    fn dct1(&self, plan: &RfftPlan<T>, c: &mut [T], fct: Norm, ortho: bool) -> Result {
+        let n = self.len;
+        if n < 2 { return Err(FftError::UnsupportedSize(n)); }
+        let sqrt2 = T::from_f64(core::f64::consts::SQRT_2);
+
+        if ortho { c[0] *= sqrt2; c[n - 1] *= sqrt2; }
+        let nn = plan.len();
+        let mut tmp = alloc::vec![T::ZERO; nn];
+        tmp[0] = c[0];
+        for i in 1..n { (tmp[i], tmp[nn - i]) = (c[i], c[i]); }
+
+        let mut spec = alloc::vec![Complex::new(T::ZERO, T::ZERO); nn / 2 + 1];
+        plan.forward(&tmp, &mut spec, fct)?;
+        for i in 0..n { c[i] = spec[i].re; }
+        if ortho { c[0] /= sqrt2; c[n - 1] /= sqrt2; }
+        return Ok(());
+    }
+
+    fn dst1(&self, plan: &RfftPlan<T>, c: &mut [T], fct: Norm) -> Result {
+        let n = self.len;
+        let nn = plan.len();
+        let mut tmp = alloc::vec![T::ZERO; nn];
+        for i in 0..n { (tmp[i + 1], tmp[nn - 1 - i]) = (c[i], -c[i]); }
+
+        let mut spec = alloc::vec![Complex::new(T::ZERO, T::ZERO); nn / 2 + 1];
+        plan.forward(&tmp, &mut spec, fct)?;
+        for i in 0..n { c[i] = -spec[i + 1].im; }
+        return Ok(());
+    }
+
+    fn dcst2(&self, plan: &RfftPlan<T>, tw: &[T], c: &mut [T], fct: Norm, ortho: bool) -> Result {
+        let n = self.len;
+        let ns2 = n - n / 2;
+        let (two, half) = (T::from_f64(2.0), T::from_f64(0.5));
+
+        if !self.cosine { for k in (1..n).step_by(2) { c[k] = -c[k]; } }
+        c[0] *= two;
+        if n & 1 == 0 { c[n - 1] *= two; }
+        for k in (1..n - 1).step_by(2) { (c[k], c[k + 1]) = (c[k] + c[k + 1], c[k + 1] - c[k]); }
+
+        let mut spec = alloc::vec![Complex::new(T::ZERO, T::ZERO); n / 2 + 1];
+        unpack(c, &mut spec);
+        plan.backward(&spec, c, fct)?;
+
+        for k in 1..ns2 {
+            let kc = n - k;
+            let t1 = tw[k - 1] * c[kc] + tw[kc - 1] * c[k];
+            let t2 = tw[k - 1] * c[k] - tw[kc - 1] * c[kc];
+            (c[k], c[kc]) = (half * (t1 + t2), half * (t1 - t2));
+        }
+        if n & 1 == 0 { c[ns2] *= tw[ns2 - 1]; }
+
+        if !self.cosine { c.reverse(); }
+        if ortho {
+            let sqrt_half = T::from_f64(core::f64::consts::FRAC_1_SQRT_2);
+            if self.cosine { c[0] *= sqrt_half; } else { c[n - 1] *= sqrt_half; }
+        }
+        return Ok(());
+    }
+
+    fn dcst3(&self, plan: &RfftPlan<T>, tw: &[T], c: &mut [T], fct: Norm, ortho: bool) -> Result {
+        let n = self.len;
+        let ns2 = n - n / 2;
+        let two = T::from_f64(2.0);
+
+        if ortho {
+            let sqrt2 = T::from_f64(core::f64::consts::SQRT_2);
+            if self.cosine { c[0] *= sqrt2; } else { c[n - 1] *= sqrt2; }
+        }
+        if !self.cosine { c.reverse(); }
+
+        for k in 1..ns2 {
+            let kc = n - k;
+            let (t1, t2) = (c[k] + c[kc], c[k] - c[kc]);
+            (c[k], c[kc]) = (tw[k - 1] * t2 + tw[kc - 1] * t1, tw[k - 1] * t1 - tw[kc - 1] * t2);
+        }
+        if n & 1 == 0 { c[ns2] *= two * tw[ns2 - 1]; }
+
+        let tmp = c.to_vec();
+        let mut spec = alloc::vec![Complex::new(T::ZERO, T::ZERO); n / 2 + 1];
+        plan.forward(&tmp, &mut spec, fct)?;
+        pack(&spec, c);
+
+        for k in (1..n - 1).step_by(2) { (c[k], c[k + 1]) = (c[k] - c[k + 1], c[k + 1] + c[k]); }
+        if !self.cosine { for k in (1..n).step_by(2) { c[k] = -c[k]; } }
+        return Ok(());
+    }
+
+    fn dcst4(&self, plan: &CfftPlan<T>, pre: &[Complex<T>], post: &[Complex<T>], c: &mut [T], fct: Norm) -> Result {
+        let n = self.len;
+        let two = T::from_f64(2.0);
+        if !self.cosine { c.reverse(); }
+
+        if n & 1 == 0 {
+            let n2 = n / 2;
+            let mut y: Vec<Complex<T>> = (0..n2).map(|i| Complex::new(c[2 * i], c[n - 1 - 2 * i]) * pre[i]).collect();
+            plan.forward(&mut y, fct)?;
+            for i in 0..n2 {
+                let ic = n2 - 1 - i;
+                c[2 * i] = two * (y[i] * post[i]).re;
+                c[2 * i + 1] = -two * (y[ic] * post[ic]).im;
+            }
+        }
+        else {
+            let mut y = alloc::vec![Complex::new(T::ZERO, T::ZERO); 2 * n];
+            for i in 0..n { y[i] = pre[i] * c[i]; }
+            plan.forward(&mut y, fct)?;
+            for k in 0..n { c[k] = two * (y[k] * post[k]).re; }
+        }
+
+        if !self.cosine { for k in (1..n).step_by(2) { c[k] = -c[k]; } }
+        return Ok(());
+    }
+}
+
+// fftpack style halfcomplex [r0, r1, i1, r2, i2, ...] to and from the half spectrum
+fn unpack<T: Float>(c: &[T], spec: &mut [Complex<T>]) {
+    let n = c.len();
+    spec[0] = Complex::new(c[0], T::ZERO);
+    for k in 1..=(n - 1) / 2 { spec[k] = Complex::new(c[2 * k - 1], c[2 * k]); }
+    if n & 1 == 0 { spec[n / 2] = Complex::new(c[n - 1], T::ZERO); }
+}
+
+fn pack<T: Float>(spec: &[Complex<T>], c: &mut [T]) {
+    let n = c.len();
+    c[0] = spec[0].re;
+    for k in 1..=(n - 1) / 2 { (c[2 * k - 1], c[2 * k]) = (spec[k].re, spec[k].im); }
+    if n & 1 == 0 { c[n - 1] = spec[n / 2].re; }
+}
+
+pub struct DctPlan<T = f64>(R2r<T>);
+
+impl<T: Float> DctPlan<T> {
+    pub fn new(length: usize, kind: DctType) -> Self { DctPlan(R2r::new(length, kind, true)) }
+
+    pub fn forward(&self, data: &mut [T], norm: Norm) -> Result { return self.0.fft(data, norm, -1); }
+    pub fn backward(&self, data: &mut [T], norm: Norm) -> Result { return self.0.fft(data, norm, 1); }
+
+    pub fn len(&self) -> usize { self.0.len }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+    pub fn kind(&self) -> DctType { self.0.kind }
+}
+
+pub struct DstPlan<T = f64>(R2r<T>);
+
+impl<T: Float> DstPlan<T> {
+    pub fn new(length: usize, kind: DctType) -> Self { DstPlan(R2r::new(length, kind, false)) }
+
+    pub fn forward(&self, data: &mut [T], norm: Norm) -> Result { return self.0.fft(data, norm, -1); }
+    pub fn backward(&self, data: &mut [T], norm: Norm) -> Result { return self.0.fft(data, norm, 1); }
+
+    pub fn len(&self) -> usize { self.0.len }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+    pub fn kind(&self) -> DctType { self.0.kind }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{err_real, signal};
+    use core::f64::consts::PI;
+
+    // the unnormalised sums, same as scipy.fft with norm="backward"
+    fn direct(x: &[f64], kind: DctType, cosine: bool) -> Vec<f64> {
+        let n = x.len();
+        let (nf, cs) = (n as f64, |a: f64| if cosine { libm::cos(a) } else { libm::sin(a) });
+        return (0..n).map(|k| {
+            let (kf, alt) = (k as f64, if k & 1 == 0 { 1.0 } else { -1.0 });
+            match (kind, cosine) {
+                (DctType::I, true) => x[0] + alt * x[n - 1]
+                    + 2.0 * (1..n - 1).map(|j| x[j] * cs(PI * kf * j as f64 / (nf - 1.0))).sum::<f64>(),
+                (DctType::I, false) => 2.0 * (0..n).map(|j| x[j] * cs(PI * (kf + 1.0) * (j as f64 + 1.0) / (nf + 1.0))).sum::<f64>(),
+                (DctType::II, true) => 2.0 * (0..n).map(|j| x[j] * cs(PI * kf * (2.0 * j as f64 + 1.0) / (2.0 * nf))).sum::<f64>(),
+                (DctType::II, false) => 2.0 * (0..n).map(|j| x[j] * cs(PI * (kf + 1.0) * (2.0 * j as f64 + 1.0) / (2.0 * nf))).sum::<f64>(),
+                (DctType::III, true) => x[0] + 2.0 * (1..n).map(|j| x[j] * cs(PI * (2.0 * kf + 1.0) * j as f64 / (2.0 * nf))).sum::<f64>(),
+                (DctType::III, false) => alt * x[n - 1]
+                    + 2.0 * (0..n - 1).map(|j| x[j] * cs(PI * (2.0 * kf + 1.0) * (j as f64 + 1.0) / (2.0 * nf))).sum::<f64>(),
+                (DctType::IV, _) => 2.0 * (0..n).map(|j| x[j] * cs(PI * (2.0 * kf + 1.0) * (2.0 * j as f64 + 1.0) / (4.0 * nf))).sum::<f64>()
+            }
+        }).collect();
+    }
+
+    const KINDS: [DctType; 4] = [DctType::I, DctType::II, DctType::III, DctType::IV];
+
+    #[test]
+    fn matches_direct_sums() {
+        for kind in KINDS {
+            for n in (1..=33).chain([64, 100, 127, 210]) {
+                let x = signal(n, n as u64);
+                for cosine in [true, false] {
+                    if cosine && kind == DctType::I && n < 2 { continue; }
+                    let mut y = x.clone();
+                    let plan = R2r::<f64>::new(n, kind, cosine);
+                    plan.fft(&mut y, Norm::Backward, -1).unwrap();
+                    assert!(err_real(&y, &direct(&x, kind, cosine)) < 1e-13, "{:?} cosine = {} n = {}", kind, cosine, n);
+                    plan.fft(&mut y, Norm::Backward, 1).unwrap();
+                    assert!(err_real(&y, &x) < 1e-13, "{:?} cosine = {} n = {}", kind, cosine, n);
+                }
+            }
+        }
+    }
+
+    // orthonormal scaling keeps the energy and undoes itself
+    #[test]
+    fn ortho_is_unitary() {
+        for kind in KINDS {
+            for n in [2, 7, 16, 45] {
+                let x = signal(n, 11);
+                let energy = |v: &[f64]| v.iter().map(|a| a * a).sum::<f64>();
+                let mut c = x.clone();
+                let dct = DctPlan::<f64>::new(n, kind);
+                dct.forward(&mut c, Norm::Ortho).unwrap();
+                assert!((energy(&c) - energy(&x)).abs() < 1e-12 * energy(&x), "dct {:?} n = {}", kind, n);
+                dct.backward(&mut c, Norm::Ortho).unwrap();
+                assert!(err_real(&c, &x) < 1e-13);
+                let mut s = x.clone();
+                let dst = DstPlan::<f64>::new(n, kind);
+                dst.forward(&mut s, Norm::Ortho).unwrap();
+                assert!((energy(&s) - energy(&x)).abs() < 1e-12 * energy(&x), "dst {:?} n = {}", kind, n);
+                dst.backward(&mut s, Norm::Ortho).unwrap();
+                assert!(err_real(&s, &x) < 1e-13);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_plans() {
+        for kind in KINDS {
+            DctPlan::<f64>::new(0, kind).forward(&mut [], Norm::Backward).unwrap();
+            DstPlan::<f64>::new(0, kind).backward(&mut [], Norm::Ortho).unwrap();
+        }
+        assert_eq!(DctPlan::<f64>::new(1, DctType::I).forward(&mut [1.0], Norm::Backward), Err(FftError::UnsupportedSize(1)));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    algorithms::{cooleytukey::{factorize, CooleyTukey}, info::PlanInfo, Algorithm, CfftPlan, Shared},
+    error::{check_finite, check_len, check_scratch},
+    Complex, Float, Result
+};
+
+use alloc::{boxed::Box, vec::Vec};
+
+// prime factor algorithm. with n = n1 n2 and gcd(n1, n2) = 1, ruritanian input indexing
+// (a n2 + b n1) mod n and crt output indexing turn the dft into an n2 x n1 two dimensional one
+// with no twiddles in between. rows and columns get their own plans, which may split again
+pub struct GoodThomas<T = f64> {
+    n: usize,
+    n1: usize,
+    rows: Box<CfftPlan<T>>, // length n1
+    cols: Box<CfftPlan<T>>, // length n2
+    gather: Vec<usize>,
+    scatter: Vec<usize>
+}
+
+// most balanced way to split n into two coprime parts, none if n is a prime power
+pub fn split(n: usize) -> Option<(usize, usize)> {
+    let mut powers: Vec<(usize, usize)> = Vec::new(); // (prime, prime power)
+    for f in factorize::<f64>(n) {
+        let (p, q) = if f.fct == 4 { (2, 4) } else { (f.fct, f.fct) };
+        match powers.iter_mut().find(|x| x.0 == p) {
+            Some(x) => x.1 *= q,
+            None => powers.push((p, q))
+        }
+    }
+    if powers.len() < 2 { return None; }
+
+    // a handful of distinct primes at most, trying every subset is fine
+    let mut best = powers[0].1;
+    for mask in 1..(1usize << powers.len()) - 1 {
+        let n1: usize = powers.iter().enumerate().filter(|(i, _)| mask >> i & 1 == 1).map(|(_, x)| x.1).product();
+        if n1.abs_diff(n / n1) < best.abs_diff(n / best) { best = n1; }
+    }
+    return Some((best, n / best));
+}
+
+// x^-1 mod m for coprime x and m
+fn inverse(x: usize, m: usize) -> usize {
+    let (mut r0, mut r1) = (m as i128, (x % m) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    return t0.rem_euclid(m as i128) as usize;
+}
+
+impl<T: Float> GoodThomas<T> {
+    pub fn new(length: usize) -> GoodThomas<T> { Self::with_inner(length, &mut |n| Shared::new(CooleyTukey::new(n))) }
+
+    // `inner` hands out the cooley-tukey plans the rows and columns are built on
+    pub fn with_inner(length: usize, inner: &mut impl FnMut(usize) -> Shared<CooleyTukey<T>>) -> GoodThomas<T> {
+        let n = length;
+        let (n1, n2) = split(n).unwrap_or((n, 1));
+
+        let mut gather = alloc::vec![0; n];
+        for b in 0..n2 {
+            for a in 0..n1 { gather[b * n1 + a] = (a * n2 + b * n1) % n; }
+        }
+
+        // k = k1 mod n1 and k = k2 mod n2
+        let e1 = (n2 as u128 * inverse(n2, n1) as u128 % n as u128) as usize;
+        let e2 = (n1 as u128 * inverse(n1, n2) as u128 % n as u128) as usize;
+        // rows come back transposed, so scatter reads column-major
+        let mut scatter = alloc::vec![0; n];
+        for k1 in 0..n1 {
+            for k2 in 0..n2 { scatter[k1 * n2 + k2] = ((k1 as u128 * e1 as u128 + k2 as u128 * e2 as u128) % n as u128) as usize; }
+        }
+
+        let (rows, cols) = (Box::new(CfftPlan::with_inner(n1, inner)), Box::new(CfftPlan::with_inner(n2, inner)));
+        return GoodThomas { n, n1, rows, cols, gather, scatter };
+    }
+
+    pub fn forward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, -1); }
+    pub fn backward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, 1); }
+
+    pub fn fft(&self, data: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+        return self.fft_with_scratch(data, &mut scratch, fct, sign);
+    }
+
+    pub fn fft_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        check_len(self.n, data.len())?;
+        check_scratch(self.scratch_len(), scratch.len())?;
+        check_finite(data)?;
+        let (n1, n2) = (self.n1, self.n / self.n1);
+        let (buf, scratch) = scratch.split_at_mut(self.n);
+
+        // rows in buf, transposed into data so the columns are contiguous too, then scattered
+        // back out through buf
+        for (b, &j) in buf.iter_mut().zip(self.gather.iter()) { *b = data[j]; }
+        self.rows.fft_batch(buf, n2, 1, n1, &mut scratch[..self.rows.batch_scratch_len()], fct, sign)?;
+        for b in 0..n2 {
+            for a in 0..n1 { data[a * n2 + b] = buf[b * n1 + a]; }
+        }
+        self.cols.fft_batch(data, n1, 1, n2, &mut scratch[..self.cols.batch_scratch_len()], T::ONE, sign)?;
+        for (&d, &k) in data.iter().zip(self.scatter.iter()) { buf[k] = d; }
+        data.copy_from_slice(buf);
+        return Ok(());
+    }
+
+    pub fn len(&self) -> usize { self.n }
+    pub fn scratch_len(&self) -> usize { self.n + self.rows.batch_scratch_len().max(self.cols.batch_scratch_len()) }
+
+    // no twiddles, only the two index maps
+    pub fn describe(&self) -> PlanInfo {
+        let (rows, cols) = (self.rows.describe(), self.cols.describe());
+        let (n1, n2) = (self.n1, self.n / self.n1);
+        return PlanInfo {
+            algorithm: Algorithm::Gt, len: self.n, real: false, factors: Vec::new(), padded_len: None,
+            twiddle_bytes: (self.gather.len() + self.scatter.len()) * core::mem::size_of::<usize>(),
+            flops: n2 as f64 * rows.flops + n1 as f64 * cols.flops + 4.0 * self.n as f64, children: alloc::vec![rows, cols]
+        };
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{math::largest_prime_factor, testing::{complex_signal, dft, err}};
+
+    fn gcd(a: usize, b: usize) -> usize { if b == 0 { a } else { gcd(b, a % b) } }
+
+    #[test]
+    fn splits_are_coprime() {
+        for n in 2..2000 {
+            match split(n) {
+                Some((n1, n2)) => assert!(n1 * n2 == n && n1 > 1 && n2 > 1 && gcd(n1, n2) == 1, "n = {}", n),
+                None => {
+                    // a prime power, nothing coprime to split off
+                    let (p, mut m) = (largest_prime_factor(n), n);
+                    while m % p == 0 { m /= p; }
+                    assert_eq!(m, 1, "n = {}", n);
+                }
+            }
+        }
+        assert_eq!(split(720), Some((16, 45)));
+        assert_eq!(inverse(7, 45) * 7 % 45, 1);
+    }
+
+    #[test]
+    fn matches_dft() {
+        // smooth on both sides, then a large prime on one that goes to rader or bluestein
+        for n in [6, 12, 15, 35, 72, 210, 720, 146, 201, 2018] {
+            let x = complex_signal(n, n as u64);
+            let plan = GoodThomas::<f64>::new(n);
+            let mut y = x.clone();
+            plan.forward(&mut y, 1.0).unwrap();
+            assert!(err(&y, &dft(&x, -1)) < 1e-13, "n = {}", n);
+            plan.backward(&mut y, 1.0 / n as f64).unwrap();
+            assert!(err(&y, &x) < 1e-13, "n = {}", n);
+        }
+    }
+
+    // good-thomas only pays off when it keeps a large prime away from a cooley-tukey pass
+    #[test]
+    fn planner_keeps_it_to_large_primes() {
+        for n in 2..1000 {
+            if matches!(CfftPlan::<f64>::new(n), CfftPlan::Gt(_)) {
+                assert!(largest_prime_factor(n) > libm::sqrt(n as f64) as usize, "n = {}", n);
+            }
+        }
+        assert!(matches!(CfftPlan::<f64>::new(2018), CfftPlan::Gt(_)));
+        assert!(matches!(CfftPlan::<f64>::new(720), CfftPlan::Ct(_)));
+    }
+}
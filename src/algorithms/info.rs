@@ -0,0 +1,115 @@
+use crate::algorithms::Algorithm;
+
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+// what a plan was built as, down through the plans nested in it
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanInfo {
+    pub algorithm: Algorithm,
+    pub len: usize,
+    pub real: bool, // takes real input, so about half the work of a complex plan of the same length
+    pub factors: Vec<usize>, // cooley-tukey radices in pass order, empty for the others
+    pub padded_len: Option<usize>, // bluestein's convolution length
+    pub twiddle_bytes: usize, // twiddle, kernel and index tables held by this plan alone
+    pub flops: f64, // rough count along the lines of cost_guess, children included
+    pub children: Vec<PlanInfo>
+}
+
+impl PlanInfo {
+    pub fn total_twiddle_bytes(&self) -> usize {
+        return self.twiddle_bytes + self.children.iter().map(|c| c.total_twiddle_bytes()).sum::<usize>();
+    }
+
+    fn write(&self, f: &mut Formatter<'_>, depth: usize) -> core::fmt::Result {
+        let name = match self.algorithm {
+            Algorithm::Ct => "cooley-tukey",
+            Algorithm::Bs => "bluestein",
+            Algorithm::Rd => "rader",
+            Algorithm::Gt => "good-thomas"
+        };
+        write!(f, "{:indent$}{}{} {}", "", if self.real { "real " } else { "" }, name, self.len, indent = 2 * depth)?;
+        if !self.factors.is_empty() { write!(f, " {:?}", self.factors)?; }
+        if let Some(n2) = self.padded_len { write!(f, " padded to {}", n2)?; }
+        writeln!(f, ": ~{:.0} flops, {} twiddle bytes", self.flops, self.twiddle_bytes)?;
+        for c in &self.children { c.write(f, depth + 1)?; }
+        return Ok(());
+    }
+}
+
+// one line per plan, nested plans indented under the one using them
+impl Display for PlanInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result { self.write(f, 0) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::cooleytukey::CooleyTukey, math::cost_guess, CfftPlan, PlanBuilder, RfftPlan};
+    use alloc::{format, string::String};
+
+    #[test]
+    fn tiny_plans_cost_nothing() {
+        for n in [0, 1] {
+            let bs = PlanBuilder::new(n).algorithm(Algorithm::Bs).build::<f64>().unwrap();
+            for info in [CfftPlan::<f64>::new(n).describe(), RfftPlan::<f64>::new(n).describe(), bs.describe()] {
+                assert_eq!(info.len, n);
+                assert_eq!(info.flops, 0.0, "{}", info);
+            }
+        }
+    }
+
+    #[test]
+    fn cooley_tukey() {
+        let info = CfftPlan::<f64>::new(1000).describe();
+        assert_eq!(info.algorithm, Algorithm::Ct);
+        assert_eq!(info.factors, CooleyTukey::<f64>::new(1000).factors());
+        assert_eq!(info.factors.iter().product::<usize>(), 1000);
+        assert_eq!(info.flops, cost_guess(1000));
+
+        // (ip - 1) (ido - 1) twiddles per pass, none of them a general radix
+        let mut l1 = 1;
+        let mut twiddles = 0;
+        for &ip in &info.factors {
+            twiddles += (ip - 1) * (1000 / (l1 * ip) - 1);
+            l1 *= ip;
+        }
+        assert_eq!(info.twiddle_bytes, twiddles * 16);
+        assert_eq!(format!("{}", info), format!("cooley-tukey 1000 {:?}: ~{:.0} flops, {} twiddle bytes\n", info.factors, info.flops, info.twiddle_bytes));
+    }
+
+    // a real plan says so, and costs half the complex one it would otherwise look like
+    #[test]
+    fn real_plans() {
+        let (real, complex) = (RfftPlan::<f64>::new(1000).describe(), CfftPlan::<f64>::new(1000).describe());
+        assert!(real.real && !complex.real);
+        assert_eq!((real.algorithm, real.len, real.flops), (Algorithm::Ct, 1000, 0.5 * complex.flops));
+        assert_ne!(real, complex);
+        assert_eq!(format!("{}", real), format!("real cooley-tukey 1000 {:?}: ~{:.0} flops, {} twiddle bytes\n", real.factors, real.flops, real.twiddle_bytes));
+
+        // bluestein runs the real transform through its complex convolution, which stays complex
+        let bs = RfftPlan::<f64>::new(1009).describe();
+        assert_eq!((bs.algorithm, bs.real, bs.children[0].real), (Algorithm::Bs, true, false));
+        assert!(format!("{}", bs).starts_with("real bluestein 1009 padded to"));
+    }
+
+    #[test]
+    fn nested_plans() {
+        let bs = CfftPlan::<f64>::new(709).describe();
+        assert_eq!((bs.algorithm, bs.padded_len), (Algorithm::Bs, Some(1440)));
+        assert_eq!(bs.children[0].len, 1440);
+        assert_eq!(bs.flops, 3.0 * cost_guess(1440));
+        assert_eq!(bs.total_twiddle_bytes(), bs.twiddle_bytes + bs.children[0].twiddle_bytes);
+
+        let gt = CfftPlan::<f64>::new(2018).describe();
+        let shape = |info: &PlanInfo| (info.algorithm, info.len);
+        assert_eq!(shape(&gt), (Algorithm::Gt, 2018));
+        assert_eq!(gt.children.iter().map(shape).collect::<Vec<_>>(), [(Algorithm::Ct, 2), (Algorithm::Rd, 1009)]);
+        assert_eq!(shape(&gt.children[1].children[0]), (Algorithm::Ct, 1008));
+
+        let text = format!("{}", gt);
+        let lines: Vec<String> = text.lines().map(|l| l.split(':').next().unwrap().into()).collect();
+        let (rows, conv) = (&gt.children[0].factors, &gt.children[1].children[0].factors);
+        assert_eq!(lines, [String::from("good-thomas 2018"), format!("  cooley-tukey 2 {:?}", rows), "  rader 1009".into(), format!("    cooley-tukey 1008 {:?}", conv)]);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{
+    algorithms::dct::{DctPlan, DctType},
+    error::check_len,
+    math::bessel_i0_scaled, FftError, Float, Norm, Result
+};
+
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MdctWindow {
+    Sine,
+    Kbd(f64) // alpha, 4 is the usual pick for long blocks and 6 for short ones
+}
+
+impl MdctWindow {
+    // both satisfy princen-bradley, w[n]^2 + w[n + len / 2]^2 = 1
+    pub fn build<T: Float>(self, len: usize) -> Result<Vec<T>> {
+        let half = len / 2;
+        let mut w = alloc::vec![0.0; len];
+        match self {
+            Self::Sine => {
+                for (n, v) in w.iter_mut().enumerate() { *v = libm::sin(PI * (n as f64 + 0.5) / len as f64); }
+            }
+            Self::Kbd(alpha) => {
+                if !(alpha >= 0.0 && alpha.is_finite()) { return Err(FftError::InvalidParameter("kbd alpha must be finite and non-negative")); }
+                // kaiser samples over i0(pi alpha), the scaling cancels out of acc / total anyway
+                let kaiser = |j: usize| {
+                    let r = 2.0 * j as f64 / half as f64 - 1.0;
+                    let a = PI * alpha * libm::sqrt((1.0 - r * r).max(0.0));
+                    bessel_i0_scaled(a) / bessel_i0_scaled(PI * alpha) * libm::exp(a - PI * alpha)
+                };
+                let total: f64 = (0..=half).map(kaiser).sum();
+                let mut acc = 0.0;
+                for n in 0..half {
+                    acc += kaiser(n);
+                    w[n] = libm::sqrt(acc / total);
+                    w[len - 1 - n] = w[n];
+                }
+            }
+        }
+        return Ok(w.into_iter().map(T::from_f64).collect());
+    }
+}
+
+// len input samples in, len / 2 coefficients out. the frame gets folded into a
+// dct-iv of half the size, which itself runs on a complex fft of len / 4
+pub struct MdctPlan<T = f64> {
+    len: usize,
+    dct: DctPlan<T>,
+    window: Vec<T>
+}
+
+impl<T: Float> MdctPlan<T> {
+    pub fn new(length: usize, window: MdctWindow) -> Result<Self> {
+        if length == 0 || length & 3 != 0 { return Err(FftError::UnsupportedSize(length)); }
+        return Ok(MdctPlan { len: length, dct: DctPlan::new(length / 2, DctType::IV), window: window.build(length)? });
+    }
+
+    pub fn forward(&self, input: &[T], output: &mut [T], norm: Norm) -> Result {
+        check_len(self.len, input.len())?;
+        check_len(self.len / 2, output.len())?;
+        let h = self.len / 4;
+        let x = |n: usize| input[n] * self.window[n];
+
+        for n in 0..h {
+            output[n] = -x(3 * h - 1 - n) - x(3 * h + n);
+            output[h + n] = x(n) - x(2 * h - 1 - n);
+        }
+        // the dct-iv plan carries a factor of 2 the mdct doesn't have. norms are relative to
+        // len / 4 so that Backward reconstructs exactly after overlap-add and Ortho is sqrt(4 / len)
+        return self.dct.forward(output, Norm::Custom(0.5 * norm.forward_factor(h)));
+    }
+
+    pub fn backward(&self, input: &[T], output: &mut [T], norm: Norm) -> Result {
+        check_len(self.len / 2, input.len())?;
+        check_len(self.len, output.len())?;
+        let (h, m) = (self.len / 4, self.len / 2);
+
+        let mut u = input.to_vec();
+        self.dct.forward(&mut u, Norm::Custom(0.5 * norm.backward_factor(h)))?;
+
+        for n in 0..h {
+            output[n] = u[h + n];
+            output[h + n] = -u[m - 1 - n];
+            output[2 * h + n] = -u[h - 1 - n];
+            output[3 * h + n] = -u[n];
+        }
+        for (o, &w) in output.iter_mut().zip(&self.window) { *o *= w; }
+        return Ok(());
+    }
+
+    // time domain alias cancellation: adds the first half of a freshly inverted frame to
+    // the tail kept from the previous one and stores the new tail for the next call
+    pub fn overlap_add(&self, frame: &[T], overlap: &mut [T], output: &mut [T]) -> Result {
+        check_len(self.len, frame.len())?;
+        check_len(self.len / 2, overlap.len())?;
+        check_len(self.len / 2, output.len())?;
+        let m = self.len / 2;
+
+        for n in 0..m {
+            output[n] = overlap[n] + frame[n];
+            overlap[n] = frame[m + n];
+        }
+        return Ok(());
+    }
+
+    pub fn len(&self) -> usize { self.len }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+    pub fn window(&self) -> &[T] { &self.window }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{err_real, signal};
+
+    const WINDOWS: [MdctWindow; 5] = [MdctWindow::Sine, MdctWindow::Kbd(0.0), MdctWindow::Kbd(4.0), MdctWindow::Kbd(6.0), MdctWindow::Kbd(400.0)];
+
+    #[test]
+    fn princen_bradley() {
+        for window in WINDOWS {
+            for len in [4, 16, 64, 256] {
+                let w: Vec<f64> = window.build(len).unwrap();
+                assert!((0..len / 2).all(|n| (w[n] * w[n] + w[n + len / 2] * w[n + len / 2] - 1.0).abs() < 1e-14), "{:?} {}", window, len);
+            }
+        }
+    }
+
+    // X[k] = sum of w[n] x[n] cos(pi / m (n + 1 / 2 + m / 2) (k + 1 / 2)) with m = len / 2
+    #[test]
+    fn matches_direct_sum() {
+        for len in [4, 8, 12, 20, 64, 120] {
+            let plan = MdctPlan::<f64>::new(len, MdctWindow::Sine).unwrap();
+            let x = signal(len, len as u64);
+            let mut y = alloc::vec![0.0; len / 2];
+            plan.forward(&x, &mut y, Norm::Backward).unwrap();
+            let m = (len / 2) as f64;
+            let want: Vec<f64> = (0..len / 2).map(|k| {
+                (0..len).map(|n| plan.window()[n] * x[n] * libm::cos(PI / m * (n as f64 + 0.5 + m / 2.0) * (k as f64 + 0.5))).sum()
+            }).collect();
+            assert!(err_real(&y, &want) < 1e-13, "len = {}", len);
+        }
+    }
+
+    // frames hop by half their length; after overlap-add everything past the first half frame
+    // comes back as it went in
+    #[test]
+    fn tdac_reconstructs() {
+        for window in WINDOWS {
+            for norm in [Norm::Backward, Norm::Ortho, Norm::Forward] {
+                let len = 32;
+                let m = len / 2;
+                let plan = MdctPlan::<f64>::new(len, window).unwrap();
+                let mut x = alloc::vec![0.0; m];
+                x.extend(signal(8 * m, 21));
+                x.extend(alloc::vec![0.0; m]);
+
+                let (mut overlap, mut out) = (alloc::vec![0.0; m], Vec::new());
+                let (mut coeffs, mut frame, mut half) = (alloc::vec![0.0; m], alloc::vec![0.0; len], alloc::vec![0.0; m]);
+                for start in (0..x.len() - m).step_by(m) {
+                    plan.forward(&x[start..start + len], &mut coeffs, norm).unwrap();
+                    plan.backward(&coeffs, &mut frame, norm).unwrap();
+                    plan.overlap_add(&frame, &mut overlap, &mut half).unwrap();
+                    out.extend_from_slice(&half);
+                }
+                assert!(err_real(&out[m..], &x[m..x.len() - m]) < 1e-13, "{:?} {:?}", window, norm);
+            }
+        }
+    }
+
+    #[test]
+    fn bad_lengths() {
+        for len in [0, 2, 6, 18] {
+            assert!(matches!(MdctPlan::<f64>::new(len, MdctWindow::Sine), Err(FftError::UnsupportedSize(_))));
+        }
+        for alpha in [-1.0, f64::NAN, f64::INFINITY] {
+            let res = MdctPlan::<f64>::new(16, MdctWindow::Kbd(alpha));
+            assert!(matches!(res, Err(FftError::InvalidParameter("kbd alpha must be finite and non-negative"))), "{}", alpha);
+        }
+    }
+}
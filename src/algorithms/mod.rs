@@ -1,47 +1,665 @@
-pub mod bluestein; pub mod cooleytukey;
-use self::{bluestein::Bluestein, cooleytukey::CooleyTukey};
+pub mod bluestein; pub mod builder; pub mod cooleytukey; pub mod czt; pub mod dct; pub mod goodthomas; pub mod info; pub mod mdct; pub mod ndim; pub mod rader; pub mod realcooleytukey;
+use self::{bluestein::Bluestein, cooleytukey::CooleyTukey, goodthomas::GoodThomas, rader::Rader, realcooleytukey::RealCooleyTukey};
+pub use self::{builder::PlanBuilder, czt::{zoom_fft, CztPlan}, dct::{DctPlan, DctType, DstPlan}, info::PlanInfo, mdct::{MdctPlan, MdctWindow}, ndim::CfftPlanNd};
 use crate::{
+    error::{check_len, check_scratch},
     math::{cost_guess, good_size, largest_prime_factor},
-    Complex, Result
+    Complex, FftError, Float, Norm, Result
 };
 
-pub enum CfftPlan {
-    Ct(CooleyTukey),
-    Bs(Bluestein)
+// how plans hold nested plans other plans may want too. arc wherever the target has one, so
+// plans stay send and sync
+#[cfg(target_has_atomic = "ptr")]
+pub type Shared<X> = alloc::sync::Arc<X>;
+#[cfg(not(target_has_atomic = "ptr"))]
+pub type Shared<X> = alloc::rc::Rc<X>;
+
+pub enum CfftPlan<T = f64> {
+    Ct(Shared<CooleyTukey<T>>),
+    Bs(Bluestein<T>),
+    Rd(Rader<T>),
+    Gt(GoodThomas<T>)
 }
 
-impl CfftPlan {
-    pub fn new(length: usize) -> Self {
-        if length < 50 || largest_prime_factor(length) <= libm::sqrt(length as f64) as usize {
-            return Self::Ct(CooleyTukey::new(length));
-        }
-        let ct_cost = cost_guess(length);
+// batches smaller than this many elements in total stay on one thread
+#[cfg(feature = "parallel")]
+const PARALLEL_GRAIN: usize = 1 << 14;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm { Ct, Bs, Rd, Gt }
+
+// rader needs a prime and good-thomas two coprime parts, the other two take anything
+pub fn check_algorithm(length: usize, algorithm: Algorithm) -> Result {
+    match algorithm {
+        Algorithm::Rd if length < 3 || largest_prime_factor(length) != length => Err(FftError::InvalidParameter("rader needs a prime length")),
+        Algorithm::Gt if length == 0 || goodthomas::split(length).is_none() => Err(FftError::InvalidParameter("good-thomas needs two coprime factors")),
+        _ => Ok(())
+    }
+}
+
+// cheapest way to do a complex transform of this length and what it roughly costs
+fn choose(length: usize) -> (Algorithm, f64) {
+    if length < 2 { return (Algorithm::Ct, 0.0); }
+    let ct_cost = cost_guess(length);
+    if length < 50 { return (Algorithm::Ct, ct_cost); }
+
+    let mut best = (Algorithm::Ct, ct_cost);
+    let lpf = largest_prime_factor(length);
+    if lpf > libm::sqrt(length as f64) as usize {
         let bs_cost = 3.0 * cost_guess(good_size(2 * length - 1));
+        if bs_cost < best.1 { best = (Algorithm::Bs, bs_cost); }
 
-        if bs_cost < ct_cost {
-            return Self::Bs(Bluestein::new(length));
+        // two transforms of p - 1, however those end up being done, plus the reordering
+        if lpf == length {
+            let rd_cost = 2.0 * choose(length - 1).1 + 2.0 * length as f64;
+            if rd_cost < best.1 { best = (Algorithm::Rd, rd_cost); }
+        }
+
+        // the same butterflies as cooley-tukey plus four shuffles of the data. on smooth lengths
+        // that's all it adds, so good-thomas only gets a look in here, where it can hand the
+        // large prime to bluestein or rader and keep the rest out of the padded convolution
+        if let Some((n1, n2)) = goodthomas::split(length) {
+            let gt_cost = n2 as f64 * choose(n1).1 + n1 as f64 * choose(n2).1 + 4.0 * length as f64;
+            if gt_cost < best.1 { best = (Algorithm::Gt, gt_cost); }
         }
-        return Self::Ct(CooleyTukey::new(length));
+    }
+    return best;
+}
+
+// whether no two lines of a batch share an element: one line after another, or each interleaved
+// into its own slot within every stride. anything else counts as overlapping, even where it isn't
+#[cfg(feature = "parallel")]
+fn lines_apart(n: usize, howmany: usize, stride: usize, dist: usize) -> bool {
+    return howmany == 1 || dist > (n - 1) * stride || (dist > 0 && (howmany - 1) * dist < stride);
+}
+
+impl<T: Float> CfftPlan<T> {
+    pub fn new(length: usize) -> Self { Self::with_inner(length, &mut |n| Shared::new(CooleyTukey::new(n))) }
+
+    // `inner` hands out the cooley-tukey plans this one is built on
+    pub(crate) fn with_inner(length: usize, inner: &mut impl FnMut(usize) -> Shared<CooleyTukey<T>>) -> Self {
+        return Self::with_algorithm(length, choose(length).0, inner);
     }
 
-    pub fn forward(&self, data: &mut [Complex], fct: f64) -> Result {
+    // the algorithm has to have passed check_algorithm for this length
+    pub(crate) fn with_algorithm(length: usize, algorithm: Algorithm, inner: &mut impl FnMut(usize) -> Shared<CooleyTukey<T>>) -> Self {
+        match algorithm {
+            Algorithm::Ct => Self::Ct(inner(length)),
+            Algorithm::Bs => Self::Bs(Bluestein::with_inner(length, inner(Bluestein::<T>::inner_len(length)))),
+            Algorithm::Rd => Self::Rd(Rader::with_inner(length, inner)),
+            Algorithm::Gt => Self::Gt(GoodThomas::with_inner(length, inner))
+        }
+    }
+
+    pub fn forward(&self, data: &mut [Complex<T>], norm: Norm) -> Result {
+        let fct = norm.fct(self.len(), -1);
         match self {
             Self::Ct(ct) => { ct.forward(data, fct) }
             Self::Bs(bs) => { bs.forward(data, fct) }
+            Self::Rd(rd) => { rd.forward(data, fct) }
+            Self::Gt(gt) => { gt.forward(data, fct) }
         }
     }
 
-    pub fn backward(&self, data: &mut [Complex], fct: f64) -> Result {
+    pub fn backward(&self, data: &mut [Complex<T>], norm: Norm) -> Result {
+        let fct = norm.fct(self.len(), 1);
         match self {
             Self::Ct(ct) => { ct.backward(data, fct) }
             Self::Bs(bs) => { bs.backward(data, fct) }
+            Self::Rd(rd) => { rd.backward(data, fct) }
+            Self::Gt(gt) => { gt.backward(data, fct) }
+        }
+    }
+
+    pub fn forward_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_with_scratch(data, scratch, norm.fct(self.len(), -1), -1);
+    }
+
+    pub fn backward_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_with_scratch(data, scratch, norm.fct(self.len(), 1), 1);
+    }
+
+    pub fn forward_into(&self, input: &[Complex<T>], output: &mut [Complex<T>], norm: Norm) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+        return self.fft_into(input, output, &mut scratch, norm.fct(self.len(), -1), -1);
+    }
+
+    pub fn backward_into(&self, input: &[Complex<T>], output: &mut [Complex<T>], norm: Norm) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+        return self.fft_into(input, output, &mut scratch, norm.fct(self.len(), 1), 1);
+    }
+
+    pub fn forward_into_with_scratch(&self, input: &[Complex<T>], output: &mut [Complex<T>], scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_into(input, output, scratch, norm.fct(self.len(), -1), -1);
+    }
+
+    pub fn backward_into_with_scratch(&self, input: &[Complex<T>], output: &mut [Complex<T>], scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_into(input, output, scratch, norm.fct(self.len(), 1), 1);
+    }
+
+    // only cooley-tukey gets anything out of having input and output apart, the rest gather from
+    // and scatter to data through their own buffers anyway
+    fn fft_into(&self, input: &[Complex<T>], output: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        match (self, sign < 0) {
+            (Self::Ct(ct), true) => { ct.forward_into(input, output, scratch, fct) }
+            (Self::Ct(ct), false) => { ct.backward_into(input, output, scratch, fct) }
+            _ => {
+                check_len(self.len(), input.len())?;
+                check_len(self.len(), output.len())?;
+                output.copy_from_slice(input);
+                self.fft_with_scratch(output, scratch, fct, sign)
+            }
+        }
+    }
+
+    pub fn forward_split(&self, re: &mut [T], im: &mut [T], norm: Norm) -> Result {
+        return self.fft_split(re, im, norm.fct(self.len(), -1), -1);
+    }
+
+    pub fn backward_split(&self, re: &mut [T], im: &mut [T], norm: Norm) -> Result {
+        return self.fft_split(re, im, norm.fct(self.len(), 1), 1);
+    }
+
+    pub fn forward_split_with_scratch(&self, re: &mut [T], im: &mut [T], scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_split_with_scratch(re, im, scratch, norm.fct(self.len(), -1), -1);
+    }
+
+    pub fn backward_split_with_scratch(&self, re: &mut [T], im: &mut [T], scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_split_with_scratch(re, im, scratch, norm.fct(self.len(), 1), 1);
+    }
+
+    fn fft_split(&self, re: &mut [T], im: &mut [T], fct: T, sign: i8) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.split_scratch_len()];
+        return self.fft_split_with_scratch(re, im, &mut scratch, fct, sign);
+    }
+
+    // cooley-tukey runs its passes on the split arrays, the rest work on a packed copy at the
+    // front of the scratch
+    fn fft_split_with_scratch(&self, re: &mut [T], im: &mut [T], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        match (self, sign < 0) {
+            (Self::Ct(ct), true) => { ct.forward_split_with_scratch(re, im, scratch, fct) }
+            (Self::Ct(ct), false) => { ct.backward_split_with_scratch(re, im, scratch, fct) }
+            _ => {
+                check_len(self.len(), re.len())?;
+                check_len(self.len(), im.len())?;
+                check_scratch(self.split_scratch_len(), scratch.len())?;
+                let (data, scratch) = scratch.split_at_mut(self.len());
+                for (c, (&r, &i)) in data.iter_mut().zip(re.iter().zip(im.iter())) { *c = Complex::new(r, i); }
+                self.fft_with_scratch(data, scratch, fct, sign)?;
+                for (c, (r, i)) in data.iter().zip(re.iter_mut().zip(im.iter_mut())) { (*r, *i) = (c.re, c.im); }
+                Ok(())
+            }
+        }
+    }
+
+    fn fft_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        match (self, sign < 0) {
+            (Self::Ct(ct), true) => { ct.forward_with_scratch(data, scratch, fct) }
+            (Self::Ct(ct), false) => { ct.backward_with_scratch(data, scratch, fct) }
+            (Self::Bs(bs), _) => { bs.fft_with_scratch(data, scratch, fct, sign) }
+            (Self::Rd(rd), _) => { rd.fft_with_scratch(data, scratch, fct, sign) }
+            (Self::Gt(gt), _) => { gt.fft_with_scratch(data, scratch, fct, sign) }
+        }
+    }
+
+    pub fn forward_batch(&self, data: &mut [Complex<T>], howmany: usize, stride: usize, dist: usize, norm: Norm) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.batch_scratch_len()];
+        return self.fft_batch(data, howmany, stride, dist, &mut scratch, norm.fct(self.len(), -1), -1);
+    }
+
+    pub fn backward_batch(&self, data: &mut [Complex<T>], howmany: usize, stride: usize, dist: usize, norm: Norm) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.batch_scratch_len()];
+        return self.fft_batch(data, howmany, stride, dist, &mut scratch, norm.fct(self.len(), 1), 1);
+    }
+
+    pub fn forward_batch_with_scratch(&self, data: &mut [Complex<T>], howmany: usize, stride: usize, dist: usize, scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_batch(data, howmany, stride, dist, scratch, norm.fct(self.len(), -1), -1);
+    }
+
+    pub fn backward_batch_with_scratch(&self, data: &mut [Complex<T>], howmany: usize, stride: usize, dist: usize, scratch: &mut [Complex<T>], norm: Norm) -> Result {
+        return self.fft_batch(data, howmany, stride, dist, scratch, norm.fct(self.len(), 1), 1);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn fft_batch(&self, data: &mut [Complex<T>], howmany: usize, stride: usize, dist: usize, scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        let n = self.len();
+        check_scratch(self.batch_scratch_len(), scratch.len())?;
+        if howmany == 0 || n == 0 { return Ok(()); }
+        if stride == 0 { return Err(FftError::UnsupportedSize(stride)); }
+
+        // a layout too big to even count can't fit either
+        let required = (howmany - 1).checked_mul(dist).zip((n - 1).checked_mul(stride))
+            .and_then(|(a, b)| a.checked_add(b)?.checked_add(1)).unwrap_or(usize::MAX);
+        if required > data.len() { return Err(FftError::LengthMismatch { expected: required, actual: data.len() }); }
+
+        #[cfg(feature = "parallel")]
+        {
+            let threads = crate::parallel::split(howmany.saturating_mul(n), PARALLEL_GRAIN);
+            // lines that share elements see each other's output one after another, which only the
+            // sequential loop below reproduces
+            if threads > 1 && lines_apart(n, howmany, stride, dist) { return self.fft_batch_parallel(data, howmany, stride, dist, threads, fct, sign); }
+        }
+
+        let (buf, scratch) = scratch.split_at_mut(n);
+        for t in 0..howmany {
+            let line = &mut data[t * dist..];
+            if stride == 1 {
+                self.fft_with_scratch(&mut line[..n], scratch, fct, sign)?;
+                continue;
+            }
+
+            for j in 0..n { buf[j] = line[j * stride]; }
+            self.fft_with_scratch(buf, scratch, fct, sign)?;
+            for j in 0..n { line[j * stride] = buf[j]; }
         }
+        return Ok(());
+    }
+
+    // each thread takes a run of whole lines with its own scratch, so the caller's goes unused.
+    // lines that sit apart are split up in place, interleaved ones go through a packed copy
+    #[cfg(feature = "parallel")]
+    #[allow(clippy::too_many_arguments)]
+    fn fft_batch_parallel(&self, data: &mut [Complex<T>], howmany: usize, stride: usize, dist: usize, threads: usize, fct: T, sign: i8) -> Result {
+        let n = self.len();
+        let lines = |part: &mut [Complex<T>], count: usize, dist: usize| {
+            let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+            for t in 0..count { self.fft_with_scratch(&mut part[t * dist..][..n], &mut scratch, fct, sign)?; }
+            return Ok(());
+        };
+
+        if stride == 1 && dist >= n {
+            let mut rest = &mut data[..];
+            let mut jobs = alloc::vec::Vec::with_capacity(threads);
+            for t in 0..threads {
+                let range = crate::parallel::chunk(howmany, threads, t);
+                let (part, tail) = rest.split_at_mut(if t + 1 < threads { range.len() * dist } else { rest.len() });
+                rest = tail;
+                jobs.push(move || lines(part, range.len(), dist));
+            }
+            return crate::parallel::run(jobs);
+        }
+
+        let mut packed = alloc::vec![Complex::new(T::ZERO, T::ZERO); howmany * n];
+        for (t, line) in packed.chunks_exact_mut(n).enumerate() {
+            for j in 0..n { line[j] = data[t * dist + j * stride]; }
+        }
+        let mut rest = &mut packed[..];
+        let mut jobs = alloc::vec::Vec::with_capacity(threads);
+        for t in 0..threads {
+            let range = crate::parallel::chunk(howmany, threads, t);
+            let (part, tail) = rest.split_at_mut(range.len() * n);
+            rest = tail;
+            jobs.push(move || lines(part, range.len(), n));
+        }
+        crate::parallel::run(jobs)?;
+        for (t, line) in packed.chunks_exact(n).enumerate() {
+            for j in 0..n { data[t * dist + j * stride] = line[j]; }
+        }
+        return Ok(());
     }
 
     pub fn len(&self) -> usize {
         match self {
             Self::Ct(ct) => { ct.len() }
             Self::Bs(bs) => { bs.len() }
+            Self::Rd(rd) => { rd.len() }
+            Self::Gt(gt) => { gt.len() }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    pub fn scratch_len(&self) -> usize {
+        match self {
+            Self::Ct(ct) => { ct.scratch_len() }
+            Self::Bs(bs) => { bs.scratch_len() }
+            Self::Rd(rd) => { rd.scratch_len() }
+            Self::Gt(gt) => { gt.scratch_len() }
+        }
+    }
+
+    pub fn batch_scratch_len(&self) -> usize { self.len() + self.scratch_len() }
+
+    pub fn split_scratch_len(&self) -> usize {
+        match self {
+            Self::Ct(ct) => { ct.split_scratch_len() }
+            _ => { self.len() + self.scratch_len() }
+        }
+    }
+
+    pub fn describe(&self) -> PlanInfo {
+        match self {
+            Self::Ct(ct) => { ct.describe() }
+            Self::Bs(bs) => { bs.describe() }
+            Self::Rd(rd) => { rd.describe() }
+            Self::Gt(gt) => { gt.describe() }
         }
     }
-}
\ No newline at end of file
+}
+
+pub enum RfftPlan<T = f64> {
+    Ct(RealCooleyTukey<T>),
+    Bs(Bluestein<T>)
+}
+
+impl<T: Float> RfftPlan<T> {
+    pub fn new(length: usize) -> Self { Self::with_inner(length, &mut |n| Shared::new(CooleyTukey::new(n))) }
+
+    pub(crate) fn with_inner(length: usize, inner: &mut impl FnMut(usize) -> Shared<CooleyTukey<T>>) -> Self {
+        if length < 50 || largest_prime_factor(length) <= libm::sqrt(length as f64) as usize {
+            return Self::Ct(RealCooleyTukey::new(length));
+        }
+        let ct_cost = 0.5 * cost_guess(length);
+        let bs_cost = 3.0 * cost_guess(good_size(2 * length - 1));
+
+        if bs_cost < ct_cost {
+            return Self::Bs(Bluestein::with_inner(length, inner(Bluestein::<T>::inner_len(length))));
+        }
+        return Self::Ct(RealCooleyTukey::new(length));
+    }
+
+    pub fn forward(&self, input: &[T], output: &mut [Complex<T>], norm: Norm) -> Result {
+        let fct = norm.fct(self.len(), -1);
+        match self {
+            Self::Ct(ct) => { ct.forward(input, output, fct) }
+            Self::Bs(bs) => { bs.forward_real(input, output, fct) }
+        }
+    }
+
+    pub fn backward(&self, input: &[Complex<T>], output: &mut [T], norm: Norm) -> Result {
+        let fct = norm.fct(self.len(), 1);
+        match self {
+            Self::Ct(ct) => { ct.backward(input, output, fct) }
+            Self::Bs(bs) => { bs.backward_real(input, output, fct) }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Ct(ct) => { ct.len() }
+            Self::Bs(bs) => { bs.len() }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    pub fn describe(&self) -> PlanInfo {
+        match self {
+            Self::Ct(ct) => { ct.describe() }
+            Self::Bs(bs) => { PlanInfo { real: true, ..bs.describe() } }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{complex_signal, dft, err, err_real, real_to_complex, signal};
+    use alloc::vec::Vec;
+
+    const RFFT_LENS: [usize; 9] = [97, 100, 128, 210, 243, 1000, 1009, 1155, 2018];
+
+    #[test]
+    fn rfft_matches_dft() {
+        for n in (1..=64).chain(RFFT_LENS) {
+            let x = signal(n, n as u64);
+            let plan = RfftPlan::<f64>::new(n);
+            let mut out = alloc::vec![Complex::new(0.0, 0.0); n / 2 + 1];
+            plan.forward(&x, &mut out, Norm::Backward).unwrap();
+            let want = dft(&real_to_complex(&x), -1);
+            assert!(err(&out, &want[..n / 2 + 1]) < 1e-13, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn rfft_round_trip() {
+        for n in (1..=64).chain(RFFT_LENS) {
+            let x = signal(n, 3 * n as u64);
+            let plan = RfftPlan::<f64>::new(n);
+            let mut spec = alloc::vec![Complex::new(0.0, 0.0); n / 2 + 1];
+            let mut back = alloc::vec![0.0; n];
+            plan.forward(&x, &mut spec, Norm::Ortho).unwrap();
+            plan.backward(&spec, &mut back, Norm::Ortho).unwrap();
+            assert!(err_real(&back, &x) < 1e-13, "n = {}", n);
+        }
+    }
+
+    // the half spectrum of a real signal is the complex transform cut short, under every norm
+    #[test]
+    fn rfft_norms_match_cfft() {
+        let n = 360;
+        let x = signal(n, 5);
+        for norm in [Norm::Backward, Norm::Ortho, Norm::Forward, Norm::Custom(0.25)] {
+            let mut half = alloc::vec![Complex::new(0.0, 0.0); n / 2 + 1];
+            RfftPlan::<f64>::new(n).forward(&x, &mut half, norm).unwrap();
+            let mut full: Vec<Complex> = real_to_complex(&x);
+            CfftPlan::<f64>::new(n).forward(&mut full, norm).unwrap();
+            assert!(err(&half, &full[..n / 2 + 1]) < 1e-14);
+        }
+    }
+
+    #[test]
+    fn rfft_rejects_wrong_lengths() {
+        let plan = RfftPlan::<f64>::new(16);
+        let mut out = alloc::vec![Complex::new(0.0, 0.0); 8];
+        assert_eq!(plan.forward(&[0.0; 16], &mut out, Norm::Backward), Err(FftError::LengthMismatch { expected: 9, actual: 8 }));
+        let mut back = [0.0; 15];
+        assert_eq!(plan.backward(&[Complex::new(0.0, 0.0); 9], &mut back, Norm::Backward), Err(FftError::LengthMismatch { expected: 16, actual: 15 }));
+    }
+
+    // one of each algorithm the planner picks, plus the tiny ones
+    const CFFT_LENS: [usize; 8] = [97, 128, 167, 210, 694, 1001, 1009, 2018];
+
+    #[test]
+    fn scratch_transforms_match_dft() {
+        for n in (1..=32).chain(CFFT_LENS) {
+            let x = complex_signal(n, n as u64);
+            let plan = CfftPlan::<f64>::new(n);
+            let mut scratch = alloc::vec![Complex::new(0.0, 0.0); plan.scratch_len()];
+            let mut y = x.clone();
+            plan.forward_with_scratch(&mut y, &mut scratch, Norm::Backward).unwrap();
+            assert!(err(&y, &dft(&x, -1)) < 1e-13, "n = {}", n);
+            plan.backward_with_scratch(&mut y, &mut scratch, Norm::Backward).unwrap();
+            assert!(err(&y, &x) < 1e-13, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn short_scratch_is_an_error() {
+        for n in [210, 1009, 2018] {
+            let plan = CfftPlan::<f64>::new(n);
+            let need = plan.scratch_len();
+            if need == 0 { continue; }
+            let mut scratch = alloc::vec![Complex::new(0.0, 0.0); need - 1];
+            let mut data = complex_signal(n, 1);
+            assert_eq!(plan.forward_with_scratch(&mut data, &mut scratch, Norm::Backward), Err(FftError::ScratchTooSmall { expected: need, actual: need - 1 }));
+        }
+    }
+
+    // every algorithm, each normalisation
+    #[test]
+    fn into_matches_dft() {
+        for (n, algorithm) in [(0, Algorithm::Ct), (1, Algorithm::Ct), (210, Algorithm::Ct), (1001, Algorithm::Ct), (167, Algorithm::Bs), (1009, Algorithm::Rd), (2018, Algorithm::Gt)] {
+            let plan = PlanBuilder::new(n).algorithm(algorithm).build::<f64>().unwrap();
+            let x = complex_signal(n, 3);
+            let (mut y, mut z) = (alloc::vec![Complex::new(0.0, 0.0); n], alloc::vec![Complex::new(0.0, 0.0); n]);
+            plan.forward_into(&x, &mut y, Norm::Ortho).unwrap();
+            let s = 1.0 / libm::sqrt(n.max(1) as f64);
+            let want: Vec<Complex> = dft(&x, -1).iter().map(|c| *c * s).collect();
+            assert!(err(&y, &want) < 1e-13, "n = {}", n);
+
+            let mut scratch = alloc::vec![Complex::new(0.0, 0.0); plan.scratch_len()];
+            plan.backward_into_with_scratch(&y, &mut z, &mut scratch, Norm::Ortho).unwrap();
+            assert!(err(&z, &x) < 1e-13, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn into_rejects_wrong_lengths() {
+        let plan = CfftPlan::<f64>::new(64);
+        let x = complex_signal(64, 1);
+        let mut out = alloc::vec![Complex::new(0.0, 0.0); 64];
+        assert_eq!(plan.forward_into(&x[..63], &mut out, Norm::Backward), Err(FftError::LengthMismatch { expected: 64, actual: 63 }));
+        assert_eq!(plan.forward_into(&x, &mut out[..63], Norm::Backward), Err(FftError::LengthMismatch { expected: 64, actual: 63 }));
+        let mut scratch = alloc::vec![Complex::new(0.0, 0.0); plan.scratch_len() - 1];
+        assert!(matches!(plan.backward_into_with_scratch(&x, &mut out, &mut scratch, Norm::Backward), Err(FftError::ScratchTooSmall { .. })));
+    }
+
+    #[test]
+    fn split_matches_dft() {
+        for (n, algorithm) in [(0, Algorithm::Ct), (1, Algorithm::Ct), (3, Algorithm::Ct), (169, Algorithm::Ct), (1001, Algorithm::Ct), (167, Algorithm::Bs), (1009, Algorithm::Rd), (2018, Algorithm::Gt)] {
+            let plan = PlanBuilder::new(n).algorithm(algorithm).build::<f64>().unwrap();
+            let x = complex_signal(n, 8);
+            let (mut re, mut im): (Vec<f64>, Vec<f64>) = x.iter().map(|c| (c.re, c.im)).unzip();
+            plan.forward_split(&mut re, &mut im, Norm::Backward).unwrap();
+            let y: Vec<Complex> = re.iter().zip(&im).map(|(&r, &i)| Complex::new(r, i)).collect();
+            assert!(err(&y, &dft(&x, -1)) < 1e-13, "n = {}", n);
+
+            plan.backward_split(&mut re, &mut im, Norm::Backward).unwrap();
+            let z: Vec<Complex> = re.iter().zip(&im).map(|(&r, &i)| Complex::new(r, i)).collect();
+            assert!(err(&z, &x) < 1e-13, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn split_rejects_bad_input() {
+        for n in [64, 167] {
+            let plan = CfftPlan::<f64>::new(n);
+            let (mut re, mut im) = (signal(n, 1), signal(n, 2));
+            assert_eq!(plan.forward_split(&mut re, &mut im[..n - 1], Norm::Backward), Err(FftError::LengthMismatch { expected: n, actual: n - 1 }));
+            assert_eq!(plan.forward_split(&mut re[..n - 1], &mut im, Norm::Backward), Err(FftError::LengthMismatch { expected: n, actual: n - 1 }));
+            im[5] = f64::NAN;
+            let res = plan.backward_split(&mut re, &mut im, Norm::Backward);
+            if cfg!(feature = "validate") { assert_eq!(res, Err(FftError::NonFiniteInput(5)), "n = {}", n); } else { assert!(res.is_ok()); }
+        }
+    }
+
+    // a scratch of split_scratch_len is enough for every algorithm and one short of it isn't
+    #[test]
+    fn split_with_scratch() {
+        for (n, algorithm) in [(0, Algorithm::Ct), (64, Algorithm::Ct), (169, Algorithm::Ct), (167, Algorithm::Bs), (1009, Algorithm::Rd), (2018, Algorithm::Gt)] {
+            let plan = PlanBuilder::new(n).algorithm(algorithm).build::<f64>().unwrap();
+            let x = complex_signal(n, 9);
+            let (mut re, mut im): (Vec<f64>, Vec<f64>) = x.iter().map(|c| (c.re, c.im)).unzip();
+            let mut scratch = alloc::vec![Complex::new(0.0, 0.0); plan.split_scratch_len()];
+            plan.forward_split_with_scratch(&mut re, &mut im, &mut scratch, Norm::Ortho).unwrap();
+            let y: Vec<Complex> = re.iter().zip(&im).map(|(&r, &i)| Complex::new(r, i)).collect();
+            let scale = if n == 0 { 1.0 } else { 1.0 / libm::sqrt(n as f64) };
+            assert!(err(&y, &dft(&x, -1).iter().map(|&c| c * scale).collect::<Vec<_>>()) < 1e-13, "n = {}", n);
+
+            // the scratch holds leftovers from the last call, which mustn't matter
+            let (mut re2, mut im2): (Vec<f64>, Vec<f64>) = x.iter().map(|c| (c.re, c.im)).unzip();
+            plan.forward_split(&mut re2, &mut im2, Norm::Ortho).unwrap();
+            plan.backward_split_with_scratch(&mut re, &mut im, &mut scratch, Norm::Ortho).unwrap();
+            let z: Vec<Complex> = re.iter().zip(&im).map(|(&r, &i)| Complex::new(r, i)).collect();
+            assert!(err(&z, &x) < 1e-13, "n = {}", n);
+            assert!(y.iter().zip(re2.iter().zip(&im2)).all(|(c, (&r, &i))| c.re == r && c.im == i), "n = {}", n);
+
+            if n > 0 {
+                scratch.pop();
+                let res = plan.forward_split_with_scratch(&mut re, &mut im, &mut scratch, Norm::Ortho);
+                assert!(matches!(res, Err(FftError::ScratchTooSmall { .. })), "n = {}", n);
+            }
+        }
+        // all fixed radices need only the one buffer, a general pass another
+        assert_eq!((CfftPlan::<f64>::new(64).split_scratch_len(), CfftPlan::<f64>::new(169).split_scratch_len()), (64, 338));
+    }
+
+    // lines that overlap give the sequential answer whatever the thread count
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn overlapping_batches_ignore_threads() {
+        let plan = CfftPlan::<f64>::new(64);
+        let x = complex_signal(64 * 1024, 6);
+        for (howmany, stride, dist) in [(2000, 1, 32), (1000, 2, 1), (1000, 1, 0), (500, 100, 3)] {
+            let run = |threads| {
+                crate::set_num_threads(threads);
+                let mut y = x.clone();
+                plan.forward_batch(&mut y, howmany, stride, dist, Norm::Ortho).unwrap();
+                return y;
+            };
+            let (one, four) = (run(1), run(4));
+            crate::set_num_threads(0);
+            assert!(one.iter().zip(&four).all(|(a, b)| a.re.to_bits() == b.re.to_bits() && a.im.to_bits() == b.im.to_bits()), "{} {} {}", howmany, stride, dist);
+        }
+        assert!(lines_apart(64, 1000, 1, 64) && lines_apart(64, 8, 8, 1) && lines_apart(1, 5, 1, 1));
+        assert!(!lines_apart(64, 1000, 1, 63) && !lines_apart(64, 9, 8, 1) && !lines_apart(1, 5, 1, 0));
+    }
+
+    // lines laid out (stride, dist) apart in a longer buffer, gaps left alone
+    fn check_batch(n: usize, howmany: usize, stride: usize, dist: usize) {
+        let len = (howmany - 1) * dist + (n - 1) * stride + 1;
+        let x = complex_signal(len, (n * howmany) as u64);
+        let mut y = x.clone();
+        CfftPlan::<f64>::new(n).forward_batch(&mut y, howmany, stride, dist, Norm::Backward).unwrap();
+        let mut touched = alloc::vec![false; len];
+        for t in 0..howmany {
+            let idx: Vec<usize> = (0..n).map(|j| t * dist + j * stride).collect();
+            let line: Vec<Complex> = idx.iter().map(|&i| x[i]).collect();
+            let got: Vec<Complex> = idx.iter().map(|&i| y[i]).collect();
+            assert!(err(&got, &dft(&line, -1)) < 1e-13, "n = {}, stride = {}, dist = {}", n, stride, dist);
+            idx.iter().for_each(|&i| touched[i] = true);
+        }
+        assert!((0..len).all(|i| touched[i] || y[i] == x[i]));
+    }
+
+    #[test]
+    fn batches_match_dft() {
+        for n in [1, 12, 97, 210] {
+            check_batch(n, 5, 1, n); // back to back
+            check_batch(n, 5, 1, n + 3); // padded rows
+            check_batch(n, 5, 5, 1); // interleaved columns
+            check_batch(n, 3, 7, 2); // columns with room in between
+        }
+    }
+
+    #[test]
+    fn bad_batches() {
+        let plan = CfftPlan::<f64>::new(8);
+        let mut data = complex_signal(16, 2);
+        assert_eq!(plan.forward_batch(&mut data, 2, 0, 8, Norm::Backward), Err(FftError::UnsupportedSize(0)));
+        assert_eq!(plan.forward_batch(&mut data, 3, 1, 8, Norm::Backward), Err(FftError::LengthMismatch { expected: 24, actual: 16 }));
+        // layouts that overflow usize don't fit either, rather than panicking or wrapping around
+        let huge = FftError::LengthMismatch { expected: usize::MAX, actual: 16 };
+        assert_eq!(plan.forward_batch(&mut data, 3, 1, usize::MAX / 2, Norm::Backward), Err(huge));
+        assert_eq!(plan.forward_batch(&mut data, 2, usize::MAX / 2, 8, Norm::Backward), Err(huge));
+        assert_eq!(plan.backward_batch(&mut data, usize::MAX, 1, 1, Norm::Backward), Err(huge));
+        let mut scratch = alloc::vec![Complex::new(0.0, 0.0); plan.batch_scratch_len() - 1];
+        assert!(matches!(plan.forward_batch_with_scratch(&mut data, 2, 1, 8, &mut scratch, Norm::Backward), Err(FftError::ScratchTooSmall { .. })));
+    }
+
+    // f32 plans land within a few float epsilons of the f64 reference
+    #[test]
+    fn f32_plans() {
+        for n in (1..=32).chain(CFFT_LENS) {
+            let x = complex_signal(n, 7 * n as u64);
+            let mut y: Vec<Complex<f32>> = x.iter().map(|v| Complex::new(v.re as f32, v.im as f32)).collect();
+            CfftPlan::<f32>::new(n).forward(&mut y, Norm::Backward).unwrap();
+            let y: Vec<Complex> = y.iter().map(|v| Complex::new(v.re as f64, v.im as f64)).collect();
+            assert!(err(&y, &dft(&x, -1)) < 1e-5, "n = {}", n);
+
+            let r: Vec<f32> = x.iter().map(|v| v.re as f32).collect();
+            let mut half = alloc::vec![Complex::new(0.0f32, 0.0); n / 2 + 1];
+            RfftPlan::<f32>::new(n).forward(&r, &mut half, Norm::Backward).unwrap();
+            let half: Vec<Complex> = half.iter().map(|v| Complex::new(v.re as f64, v.im as f64)).collect();
+            let want = dft(&real_to_complex(&r.iter().map(|&v| v as f64).collect::<Vec<_>>()), -1);
+            assert!(err(&half, &want[..n / 2 + 1]) < 1e-5, "n = {}", n);
+        }
+    }
+
+    // zero-length plans used to hang in the cost model
+    #[test]
+    fn zero_length_plans() {
+        let plan = CfftPlan::<f64>::new(0);
+        assert!(plan.is_empty());
+        plan.forward(&mut [], Norm::Ortho).unwrap();
+        plan.backward(&mut [], Norm::Backward).unwrap();
+        let mut out = [Complex::new(1.0, 1.0)];
+        RfftPlan::<f64>::new(0).forward(&[], &mut out, Norm::Backward).unwrap();
+        CfftPlanNd::<f64>::new(&[0, 3]).forward(&mut [], Norm::Backward).unwrap();
+        #[cfg(target_has_atomic = "ptr")]
+        assert!(crate::Planner::<f64>::new().plan(0).is_empty());
+        assert!(crate::RcPlanner::<f64>::new().plan(0).is_empty());
+        assert!(crate::RcPlanner::<f64>::new().plan_real(0).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{algorithms::CfftPlan, error::check_len, Complex, FftError, Float, Norm, Result};
+
+use alloc::vec::Vec;
+
+pub struct CfftPlanNd<T = f64> {
+    shape: Vec<usize>,
+    plans: Vec<CfftPlan<T>>,
+    axis_plan: Vec<usize>
+}
+
+impl<T: Float> CfftPlanNd<T> {
+    pub fn new(shape: &[usize]) -> Self {
+        let mut plans: Vec<CfftPlan<T>> = Vec::new();
+        let mut axis_plan = Vec::with_capacity(shape.len());
+
+        for &n in shape {
+            match plans.iter().position(|p| p.len() == n) {
+                Some(idx) => axis_plan.push(idx),
+                None => { axis_plan.push(plans.len()); plans.push(CfftPlan::new(n)); }
+            }
+        }
+        return CfftPlanNd { shape: shape.to_vec(), plans, axis_plan };
+    }
+
+    pub fn forward(&self, data: &mut [Complex<T>], norm: Norm) -> Result { return self.fft(data, &self.all_axes(), norm, -1); }
+    pub fn backward(&self, data: &mut [Complex<T>], norm: Norm) -> Result { return self.fft(data, &self.all_axes(), norm, 1); }
+
+    pub fn forward_axes(&self, data: &mut [Complex<T>], axes: &[usize], norm: Norm) -> Result { return self.fft(data, axes, norm, -1); }
+    pub fn backward_axes(&self, data: &mut [Complex<T>], axes: &[usize], norm: Norm) -> Result { return self.fft(data, axes, norm, 1); }
+
+    fn all_axes(&self) -> Vec<usize> { (0..self.shape.len()).collect() }
+
+    fn fft(&self, data: &mut [Complex<T>], axes: &[usize], norm: Norm, sign: i8) -> Result {
+        check_len(self.len(), data.len())?;
+        if let Some(&axis) = axes.iter().find(|&&a| a >= self.shape.len()) {
+            return Err(FftError::InvalidAxis { axis, ndim: self.shape.len() });
+        }
+        // normalised over every transformed element, like numpy's fftn
+        let fct: T = norm.fct(axes.iter().map(|&a| self.shape[a]).product(), sign);
+        if axes.is_empty() {
+            if fct != T::ONE { data.iter_mut().for_each(|d| *d *= fct); }
+            return Ok(());
+        }
+
+        let scratch_len = axes.iter().map(|&a| self.plans[self.axis_plan[a]].batch_scratch_len()).max().unwrap_or(0);
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); scratch_len];
+        let mut fct = fct;
+
+        for &axis in axes {
+            self.fft_axis(data, axis, &mut scratch, fct, sign)?;
+            fct = T::ONE;
+        }
+        return Ok(());
+    }
+
+    fn fft_axis(&self, data: &mut [Complex<T>], axis: usize, scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        let plan = &self.plans[self.axis_plan[axis]];
+        let n = self.shape[axis];
+        let stride: usize = self.shape[axis + 1..].iter().product();
+        if n == 0 || stride == 0 { return Ok(()); }
+
+        for block in data.chunks_exact_mut(n * stride) {
+            plan.fft_batch(block, stride, stride, 1, scratch, fct, sign)?;
+        }
+        return Ok(());
+    }
+
+    pub fn shape(&self) -> &[usize] { &self.shape }
+    pub fn len(&self) -> usize { self.shape.iter().product() }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{complex_signal, err, root};
+
+    // the sum over every element, with one root per transformed axis
+    fn dftn(x: &[Complex], shape: &[usize], axes: &[usize], sign: i8) -> Vec<Complex> {
+        let unravel = |mut i: usize| {
+            let mut idx = alloc::vec![0; shape.len()];
+            for a in (0..shape.len()).rev() { idx[a] = i % shape[a]; i /= shape[a]; }
+            idx
+        };
+        return (0..x.len()).map(|k| {
+            let ki = unravel(k);
+            x.iter().enumerate().fold(Complex::new(0.0, 0.0), |acc, (j, &v)| {
+                let ji = unravel(j);
+                // axes left alone only pass through matching positions
+                if (0..shape.len()).any(|a| !axes.contains(&a) && ji[a] != ki[a]) { return acc; }
+                acc + axes.iter().fold(v, |w, &a| w * root(ji[a] * ki[a], shape[a], sign))
+            })
+        }).collect();
+    }
+
+    #[test]
+    fn forward_matches_dftn() {
+        for shape in [&[6][..], &[4, 5], &[3, 4, 5], &[2, 1, 7, 3]] {
+            let plan = CfftPlanNd::<f64>::new(shape);
+            let x = complex_signal(plan.len(), shape.len() as u64);
+            let mut y = x.clone();
+            plan.forward(&mut y, Norm::Backward).unwrap();
+            let axes: Vec<usize> = (0..shape.len()).collect();
+            assert!(err(&y, &dftn(&x, shape, &axes, -1)) < 1e-13, "shape = {:?}", shape);
+            plan.backward(&mut y, Norm::Backward).unwrap();
+            assert!(err(&y, &x) < 1e-13);
+        }
+    }
+
+    // norm counts only the transformed elements
+    #[test]
+    fn some_axes() {
+        let shape = [3, 4, 5];
+        let plan = CfftPlanNd::<f64>::new(&shape);
+        let x = complex_signal(60, 9);
+        for axes in [&[][..], &[1], &[0, 2], &[2, 0]] {
+            let mut y = x.clone();
+            plan.forward_axes(&mut y, axes, Norm::Ortho).unwrap();
+            let n: usize = axes.iter().map(|&a| shape[a]).product();
+            let want: Vec<Complex> = dftn(&x, &shape, axes, -1).iter().map(|&v| v / libm::sqrt(n as f64)).collect();
+            assert!(err(&y, &want) < 1e-13, "axes = {:?}", axes);
+        }
+    }
+
+    #[test]
+    fn bad_axis_and_length() {
+        let plan = CfftPlanNd::<f64>::new(&[4, 4]);
+        let mut data = complex_signal(16, 1);
+        assert_eq!(plan.forward_axes(&mut data, &[2], Norm::Backward), Err(FftError::InvalidAxis { axis: 2, ndim: 2 }));
+        assert_eq!(plan.forward(&mut data[..15], Norm::Backward), Err(FftError::LengthMismatch { expected: 16, actual: 15 }));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    algorithms::{cooleytukey::CooleyTukey, info::PlanInfo, Algorithm, CfftPlan, Shared},
+    error::{check_finite, check_len, check_scratch},
+    math::{largest_prime_factor, sincos_2pibyn}, Complex, Float, Result
+};
+
+use alloc::{boxed::Box, vec::Vec};
+
+// prime lengths only. with g a primitive root mod p, every nonzero index is g^k for exactly one
+// k, and reordering input by g^k and output by g^-k turns the dft of everything but x[0] into a
+// cyclic convolution of length p - 1, done with a nested plan that's usually nicely composite
+pub struct Rader<T = f64> {
+    p: usize,
+    plan: Box<CfftPlan<T>>,
+    gather: Vec<usize>, // g^k mod p
+    scatter: Vec<usize>, // g^-k mod p
+    kernel: Vec<Complex<T>> // transformed w^(g^-k), 1 / (p - 1) folded in
+}
+
+fn pow_mod(b: usize, mut e: usize, m: usize) -> usize {
+    let mut r = 1u128;
+    let (mut b128, m128) = (b as u128 % m as u128, m as u128);
+    while e > 0 {
+        if e & 1 == 1 { r = r * b128 % m128; }
+        b128 = b128 * b128 % m128;
+        e >>= 1;
+    }
+    return r as usize;
+}
+
+fn primitive_root(p: usize) -> usize {
+    let mut factors = Vec::new();
+    let mut rest = p - 1;
+    while rest > 1 {
+        let f = largest_prime_factor(rest);
+        factors.push(f);
+        while rest.is_multiple_of(f) { rest /= f; }
+    }
+    return (2..p).find(|&g| factors.iter().all(|&f| pow_mod(g, (p - 1) / f, p) != 1)).unwrap_or(1);
+}
+
+impl<T: Float> Rader<T> {
+    pub fn new(length: usize) -> Rader<T> { Self::with_inner(length, &mut |n| Shared::new(CooleyTukey::new(n))) }
+
+    // `inner` hands out the cooley-tukey plans the convolution is built on
+    pub fn with_inner(length: usize, inner: &mut impl FnMut(usize) -> Shared<CooleyTukey<T>>) -> Rader<T> {
+        let p = length;
+        let n = p - 1;
+        let g = primitive_root(p);
+        let ginv = pow_mod(g, p - 2, p);
+
+        let (mut gather, mut scatter) = (alloc::vec![1; n], alloc::vec![1; n]);
+        for k in 1..n {
+            gather[k] = gather[k - 1] * g % p;
+            scatter[k] = scatter[k - 1] * ginv % p;
+        }
+
+        let mut tw = alloc::vec![Complex::new(T::ZERO, T::ZERO); p];
+        sincos_2pibyn(p, &mut tw);
+        let xn = T::from_f64(1.0 / n as f64);
+        let mut kernel: Vec<Complex<T>> = scatter.iter().map(|&j| tw[j].conj() * xn).collect();
+
+        let plan = Box::new(CfftPlan::with_inner(n, inner));
+        plan.fft_with_scratch(&mut kernel, &mut alloc::vec![Complex::new(T::ZERO, T::ZERO); plan.scratch_len()], T::ONE, -1).unwrap();
+        return Rader { p, plan, gather, scatter, kernel };
+    }
+
+    pub fn forward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, -1); }
+    pub fn backward(&self, data: &mut [Complex<T>], fct: T) -> Result { return self.fft(data, fct, 1); }
+
+    pub fn fft(&self, data: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); self.scratch_len()];
+        return self.fft_with_scratch(data, &mut scratch, fct, sign);
+    }
+
+    // the kernel is for the forward direction, backward runs it on the conjugate
+    pub fn fft_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>], fct: T, sign: i8) -> Result {
+        check_len(self.p, data.len())?;
+        check_scratch(self.scratch_len(), scratch.len())?;
+        check_finite(data)?;
+        let (buf, scratch) = scratch.split_at_mut(self.p - 1);
+        let c = |z: Complex<T>| if sign > 0 { z.conj() } else { z };
+
+        let x0 = c(data[0]);
+        let mut sum = x0;
+        for (b, &j) in buf.iter_mut().zip(self.gather.iter()) {
+            *b = c(data[j]);
+            sum += *b;
+        }
+
+        self.plan.fft_with_scratch(buf, scratch, T::ONE, -1)?;
+        for (b, &k) in buf.iter_mut().zip(self.kernel.iter()) { *b *= k; }
+        self.plan.fft_with_scratch(buf, scratch, T::ONE, 1)?;
+
+        data[0] = c(sum) * fct;
+        for (&b, &j) in buf.iter().zip(self.scatter.iter()) { data[j] = c(x0 + b) * fct; }
+        return Ok(());
+    }
+
+    pub fn len(&self) -> usize { self.p }
+    pub fn scratch_len(&self) -> usize { self.p - 1 + self.plan.scratch_len() }
+
+    pub fn describe(&self) -> PlanInfo {
+        let inner = self.plan.describe();
+        let bytes = self.kernel.len() * core::mem::size_of::<Complex<T>>() + (self.gather.len() + self.scatter.len()) * core::mem::size_of::<usize>();
+        return PlanInfo {
+            algorithm: Algorithm::Rd, len: self.p, real: false, factors: Vec::new(), padded_len: None,
+            twiddle_bytes: bytes, flops: 2.0 * inner.flops + 2.0 * self.p as f64, children: alloc::vec![inner]
+        };
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{complex_signal, dft, err};
+
+    #[test]
+    fn primitive_roots() {
+        for p in [3, 5, 7, 11, 13, 97, 1009, 65537] {
+            let g = primitive_root(p);
+            // g generates every nonzero residue, so no smaller power than p - 1 comes back to 1
+            let mut x = 1;
+            for k in 1..p - 1 {
+                x = x * g % p;
+                assert_ne!(x, 1, "p = {}, g = {}, k = {}", p, g, k);
+            }
+            assert_eq!(pow_mod(g, p - 1, p), 1);
+        }
+    }
+
+    #[test]
+    fn matches_dft() {
+        for p in [3, 5, 7, 11, 13, 17, 97, 257, 1009, 2003] {
+            let x = complex_signal(p, p as u64);
+            let plan = Rader::<f64>::new(p);
+            let mut y = x.clone();
+            plan.forward(&mut y, 1.0).unwrap();
+            assert!(err(&y, &dft(&x, -1)) < 1e-13, "p = {}", p);
+            plan.backward(&mut y, 1.0 / p as f64).unwrap();
+            assert!(err(&y, &x) < 1e-13, "p = {}", p);
+        }
+    }
+
+    // large primes where p - 1 is smooth go to rader on their own
+    #[test]
+    fn planner_picks_rader() {
+        for p in [97, 1009, 2003] {
+            assert!(matches!(CfftPlan::<f64>::new(p), CfftPlan::Rd(_)), "p = {}", p);
+        }
+    }
+}
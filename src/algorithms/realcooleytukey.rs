@@ -0,0 +1,725 @@
+use crate::{
+    algorithms::{cooleytukey::{check_factors, factorize, table_bytes, FactorData}, info::PlanInfo, Algorithm},
+    error::{check_finite, check_finite_real, check_len},
+    math::{cost_guess, sincos_2pibyn}, Complex, FftError, Float, Result
+};
+
+use alloc::vec::Vec;
+
+fn pm<T: Float>(a: T, b: T) -> (T, T) { (a + b, a - b) }
+
+fn radf2<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 2;
+
+    for k in 0..l1 {
+        (ch[ido * (cdim * k)], ch[ido - 1 + ido * (1 + cdim * k)]) = pm(cc[ido * k], cc[ido * (k + l1)]);
+    }
+    if ido & 1 == 0 {
+        for k in 0..l1 {
+            ch[ido * (1 + cdim * k)] = -cc[ido - 1 + ido * (k + l1)];
+            ch[ido - 1 + ido * (cdim * k)] = cc[ido - 1 + ido * k];
+        }
+    }
+    if ido <= 2 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let t2 = wa[(i >> 1) - 1].conj() * Complex::new(cc[i - 1 + ido * (k + l1)], cc[i + ido * (k + l1)]);
+            (ch[i - 1 + ido * (cdim * k)], ch[ic - 1 + ido * (1 + cdim * k)]) = pm(cc[i - 1 + ido * k], t2.re);
+            (ch[i + ido * (cdim * k)], ch[ic + ido * (1 + cdim * k)]) = pm(t2.im, cc[i + ido * k]);
+        }
+    }
+}
+
+fn radf3<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 3;
+    let iw = (ido - 1) >> 1;
+    let taur = T::from_f64(-0.5);
+    let taui = T::from_f64(0.8660254037844386);
+
+    for k in 0..l1 {
+        let cr2 = cc[ido * (k + l1)] + cc[ido * (k + l1 * 2)];
+        ch[ido * (cdim * k)] = cc[ido * k] + cr2;
+        ch[ido * (2 + cdim * k)] = taui * (cc[ido * (k + l1 * 2)] - cc[ido * (k + l1)]);
+        ch[ido - 1 + ido * (1 + cdim * k)] = cc[ido * k] + taur * cr2;
+    }
+    if ido == 1 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let d2 = wa[(i >> 1) - 1].conj() * Complex::new(cc[i - 1 + ido * (k + l1)], cc[i + ido * (k + l1)]);
+            let d3 = wa[(i >> 1) - 1 + iw].conj() * Complex::new(cc[i - 1 + ido * (k + l1 * 2)], cc[i + ido * (k + l1 * 2)]);
+            let c2 = d2 + d3;
+
+            ch[i - 1 + ido * (cdim * k)] = cc[i - 1 + ido * k] + c2.re;
+            ch[i + ido * (cdim * k)] = cc[i + ido * k] + c2.im;
+
+            let tr2 = cc[i - 1 + ido * k] + taur * c2.re;
+            let ti2 = cc[i + ido * k] + taur * c2.im;
+            let tr3 = taui * (d2.im - d3.im);
+            let ti3 = taui * (d3.re - d2.re);
+            (ch[i - 1 + ido * (2 + cdim * k)], ch[ic - 1 + ido * (1 + cdim * k)]) = pm(tr2, tr3);
+            (ch[i + ido * (2 + cdim * k)], ch[ic + ido * (1 + cdim * k)]) = pm(ti3, ti2);
+        }
+    }
+}
+
+fn radf4<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 4;
+    let iw = (ido - 1) >> 1;
+    let hsqt2 = T::from_f64(core::f64::consts::FRAC_1_SQRT_2);
+
+    for k in 0..l1 {
+        let tr1; let tr2;
+        (tr1, ch[ido * (2 + cdim * k)]) = pm(cc[ido * (k + l1 * 3)], cc[ido * (k + l1)]);
+        (tr2, ch[ido - 1 + ido * (1 + cdim * k)]) = pm(cc[ido * k], cc[ido * (k + l1 * 2)]);
+        (ch[ido * (cdim * k)], ch[ido - 1 + ido * (3 + cdim * k)]) = pm(tr2, tr1);
+    }
+    if ido & 1 == 0 {
+        for k in 0..l1 {
+            let ti1 = -hsqt2 * (cc[ido - 1 + ido * (k + l1)] + cc[ido - 1 + ido * (k + l1 * 3)]);
+            let tr1 = hsqt2 * (cc[ido - 1 + ido * (k + l1)] - cc[ido - 1 + ido * (k + l1 * 3)]);
+            (ch[ido - 1 + ido * (cdim * k)], ch[ido - 1 + ido * (2 + cdim * k)]) = pm(cc[ido - 1 + ido * k], tr1);
+            (ch[ido * (3 + cdim * k)], ch[ido * (1 + cdim * k)]) = pm(ti1, cc[ido - 1 + ido * (k + l1 * 2)]);
+        }
+    }
+    if ido <= 2 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let c2 = wa[(i >> 1) - 1].conj() * Complex::new(cc[i - 1 + ido * (k + l1)], cc[i + ido * (k + l1)]);
+            let c3 = wa[(i >> 1) - 1 + iw].conj() * Complex::new(cc[i - 1 + ido * (k + l1 * 2)], cc[i + ido * (k + l1 * 2)]);
+            let c4 = wa[(i >> 1) - 1 + 2 * iw].conj() * Complex::new(cc[i - 1 + ido * (k + l1 * 3)], cc[i + ido * (k + l1 * 3)]);
+
+            let (tr1, tr4) = pm(c4.re, c2.re);
+            let (ti1, ti4) = pm(c2.im, c4.im);
+            let (tr2, tr3) = pm(cc[i - 1 + ido * k], c3.re);
+            let (ti2, ti3) = pm(cc[i + ido * k], c3.im);
+
+            (ch[i - 1 + ido * (cdim * k)], ch[ic - 1 + ido * (3 + cdim * k)]) = pm(tr2, tr1);
+            (ch[i + ido * (cdim * k)], ch[ic + ido * (3 + cdim * k)]) = pm(ti1, ti2);
+            (ch[i - 1 + ido * (2 + cdim * k)], ch[ic - 1 + ido * (1 + cdim * k)]) = pm(tr3, ti4);
+            (ch[i + ido * (2 + cdim * k)], ch[ic + ido * (1 + cdim * k)]) = pm(tr4, ti3);
+        }
+    }
+}
+
+fn radf5<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 5;
+    let iw = (ido - 1) >> 1;
+    let tr11 = T::from_f64(0.30901699437494745);
+    let ti11 = T::from_f64(0.9510565162951535);
+    let tr12 = T::from_f64(-0.8090169943749475);
+    let ti12 = T::from_f64(0.5877852522924731);
+
+    for k in 0..l1 {
+        let (cr2, ci5) = pm(cc[ido * (k + l1 * 4)], cc[ido * (k + l1)]);
+        let (cr3, ci4) = pm(cc[ido * (k + l1 * 3)], cc[ido * (k + l1 * 2)]);
+        let c0 = cc[ido * k];
+
+        ch[ido * (cdim * k)] = c0 + cr2 + cr3;
+        ch[ido - 1 + ido * (1 + cdim * k)] = c0 + tr11 * cr2 + tr12 * cr3;
+        ch[ido * (2 + cdim * k)] = ti11 * ci5 + ti12 * ci4;
+        ch[ido - 1 + ido * (3 + cdim * k)] = c0 + tr12 * cr2 + tr11 * cr3;
+        ch[ido * (4 + cdim * k)] = ti12 * ci5 - ti11 * ci4;
+    }
+    if ido == 1 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let d2 = wa[(i >> 1) - 1].conj() * Complex::new(cc[i - 1 + ido * (k + l1)], cc[i + ido * (k + l1)]);
+            let d3 = wa[(i >> 1) - 1 + iw].conj() * Complex::new(cc[i - 1 + ido * (k + l1 * 2)], cc[i + ido * (k + l1 * 2)]);
+            let d4 = wa[(i >> 1) - 1 + 2 * iw].conj() * Complex::new(cc[i - 1 + ido * (k + l1 * 3)], cc[i + ido * (k + l1 * 3)]);
+            let d5 = wa[(i >> 1) - 1 + 3 * iw].conj() * Complex::new(cc[i - 1 + ido * (k + l1 * 4)], cc[i + ido * (k + l1 * 4)]);
+
+            let (cr2, ci5) = pm(d5.re, d2.re);
+            let (ci2, cr5) = pm(d2.im, d5.im);
+            let (cr3, ci4) = pm(d4.re, d3.re);
+            let (ci3, cr4) = pm(d3.im, d4.im);
+            let c0 = Complex::new(cc[i - 1 + ido * k], cc[i + ido * k]);
+
+            ch[i - 1 + ido * (cdim * k)] = c0.re + cr2 + cr3;
+            ch[i + ido * (cdim * k)] = c0.im + ci2 + ci3;
+
+            let tr2 = c0.re + tr11 * cr2 + tr12 * cr3;
+            let ti2 = c0.im + tr11 * ci2 + tr12 * ci3;
+            let tr3 = c0.re + tr12 * cr2 + tr11 * cr3;
+            let ti3 = c0.im + tr12 * ci2 + tr11 * ci3;
+            let (tr5, tr4) = (cr5 * ti11 + cr4 * ti12, cr5 * ti12 - cr4 * ti11);
+            let (ti5, ti4) = (ci5 * ti11 + ci4 * ti12, ci5 * ti12 - ci4 * ti11);
+
+            (ch[i - 1 + ido * (2 + cdim * k)], ch[ic - 1 + ido * (1 + cdim * k)]) = pm(tr2, tr5);
+            (ch[i + ido * (2 + cdim * k)], ch[ic + ido * (1 + cdim * k)]) = pm(ti5, ti2);
+            (ch[i - 1 + ido * (4 + cdim * k)], ch[ic - 1 + ido * (3 + cdim * k)]) = pm(tr3, tr4);
+            (ch[i + ido * (4 + cdim * k)], ch[ic + ido * (3 + cdim * k)]) = pm(ti4, ti3);
+        }
+    }
+}
+
+fn radfg<T: Float>(ido: usize, ip: usize, l1: usize, cc: &mut [T], ch: &mut [T], wa: &[Complex<T>], csarr: &[Complex<T>]) {
+    let cdim = ip;
+    let ipph = (ip + 1) >> 1;
+    let idl1 = ido * l1;
+    let iw = (ido - 1) >> 1;
+
+    if ido > 1 {
+        for j in 1..ipph {
+            let jc = ip - j;
+            for k in 0..l1 {
+                for i in (1..ido - 1).step_by(2) {
+                    let x1 = wa[(j - 1) * iw + (i >> 1)].conj() * Complex::new(cc[i + ido * (k + l1 * j)], cc[i + 1 + ido * (k + l1 * j)]);
+                    let x3 = wa[(jc - 1) * iw + (i >> 1)].conj() * Complex::new(cc[i + ido * (k + l1 * jc)], cc[i + 1 + ido * (k + l1 * jc)]);
+                    cc[i + ido * (k + l1 * j)] = x1.re + x3.re;
+                    cc[i + ido * (k + l1 * jc)] = x1.im - x3.im;
+                    cc[i + 1 + ido * (k + l1 * j)] = x1.im + x3.im;
+                    cc[i + 1 + ido * (k + l1 * jc)] = x3.re - x1.re;
+                }
+            }
+        }
+    }
+
+    for j in 1..ipph {
+        let jc = ip - j;
+        for k in 0..l1 {
+            let t1 = cc[ido * (k + l1 * j)];
+            let t2 = cc[ido * (k + l1 * jc)];
+            cc[ido * (k + l1 * j)] = t1 + t2;
+            cc[ido * (k + l1 * jc)] = t2 - t1;
+        }
+    }
+
+    for l in 1..ipph {
+        let lc = ip - l;
+
+        for ik in 0..idl1 {
+            ch[ik + idl1 * l] = cc[ik] + csarr[l].re * cc[ik + idl1] + csarr[2 * l].re * cc[ik + idl1 * 2];
+            ch[ik + idl1 * lc] = csarr[l].im * cc[ik + idl1 * (ip - 1)] + csarr[2 * l].im * cc[ik + idl1 * (ip - 2)];
+        }
+
+        let mut iang = 2 * l;
+        let mut j = 3;
+        let mut jc = ip - 3;
+
+        while j < ipph - 1 {
+            iang += l;
+            if iang >= ip { iang -= ip; }
+            let ar1 = csarr[iang];
+
+            iang += l;
+            if iang >= ip { iang -= ip; }
+            let ar2 = csarr[iang];
+
+            for ik in 0..idl1 {
+                ch[ik + idl1 * l] += ar1.re * cc[ik + idl1 * j] + ar2.re * cc[ik + idl1 * (j + 1)];
+                ch[ik + idl1 * lc] += ar1.im * cc[ik + idl1 * jc] + ar2.im * cc[ik + idl1 * (jc - 1)];
+            }
+            j += 2;
+            jc -= 2;
+        }
+
+        while j < ipph {
+            iang += l;
+            if iang >= ip { iang -= ip; }
+            let ar = csarr[iang];
+
+            for ik in 0..idl1 {
+                ch[ik + idl1 * l] += ar.re * cc[ik + idl1 * j];
+                ch[ik + idl1 * lc] += ar.im * cc[ik + idl1 * jc];
+            }
+            j += 1;
+            jc -= 1;
+        }
+    }
+
+    ch[..idl1].copy_from_slice(&cc[..idl1]);
+    for j in 1..ipph {
+        for ik in 0..idl1 {
+            ch[ik] += cc[ik + idl1 * j];
+        }
+    }
+
+    for k in 0..l1 {
+        for i in 0..ido {
+            cc[i + ido * (cdim * k)] = ch[i + ido * k];
+        }
+    }
+
+    for j in 1..ipph {
+        let jc = ip - j;
+        let j2 = 2 * j - 1;
+        for k in 0..l1 {
+            cc[ido - 1 + ido * (j2 + cdim * k)] = ch[ido * (k + l1 * j)];
+            cc[ido * (j2 + 1 + cdim * k)] = ch[ido * (k + l1 * jc)];
+        }
+    }
+
+    if ido == 1 { return; }
+
+    for j in 1..ipph {
+        let jc = ip - j;
+        let j2 = 2 * j - 1;
+        for k in 0..l1 {
+            for i in (1..ido - 1).step_by(2) {
+                let ic = ido - i - 2;
+                cc[i + ido * (j2 + 1 + cdim * k)] = ch[i + ido * (k + l1 * j)] + ch[i + ido * (k + l1 * jc)];
+                cc[ic + ido * (j2 + cdim * k)] = ch[i + ido * (k + l1 * j)] - ch[i + ido * (k + l1 * jc)];
+                cc[i + 1 + ido * (j2 + 1 + cdim * k)] = ch[i + 1 + ido * (k + l1 * j)] + ch[i + 1 + ido * (k + l1 * jc)];
+                cc[ic + 1 + ido * (j2 + cdim * k)] = ch[i + 1 + ido * (k + l1 * jc)] - ch[i + 1 + ido * (k + l1 * j)];
+            }
+        }
+    }
+}
+
+fn radb2<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 2;
+    let two = T::from_f64(2.0);
+
+    for k in 0..l1 {
+        (ch[ido * k], ch[ido * (k + l1)]) = pm(cc[ido * (cdim * k)], cc[ido - 1 + ido * (1 + cdim * k)]);
+    }
+    if ido & 1 == 0 {
+        for k in 0..l1 {
+            ch[ido - 1 + ido * k] = two * cc[ido - 1 + ido * (cdim * k)];
+            ch[ido - 1 + ido * (k + l1)] = -two * cc[ido * (1 + cdim * k)];
+        }
+    }
+    if ido <= 2 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let (tr2, ti2);
+            (ch[i - 1 + ido * k], tr2) = pm(cc[i - 1 + ido * (cdim * k)], cc[ic - 1 + ido * (1 + cdim * k)]);
+            (ti2, ch[i + ido * k]) = pm(cc[i + ido * (cdim * k)], cc[ic + ido * (1 + cdim * k)]);
+            let c2 = wa[(i >> 1) - 1] * Complex::new(tr2, ti2);
+            (ch[i - 1 + ido * (k + l1)], ch[i + ido * (k + l1)]) = (c2.re, c2.im);
+        }
+    }
+}
+
+fn radb3<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 3;
+    let two = T::from_f64(2.0);
+    let iw = (ido - 1) >> 1;
+    let taur = T::from_f64(-0.5);
+    let taui = T::from_f64(0.8660254037844386);
+
+    for k in 0..l1 {
+        let tr2 = two * cc[ido - 1 + ido * (1 + cdim * k)];
+        let cr2 = cc[ido * (cdim * k)] + taur * tr2;
+        ch[ido * k] = cc[ido * (cdim * k)] + tr2;
+        let ci3 = two * taui * cc[ido * (2 + cdim * k)];
+        (ch[ido * (k + l1 * 2)], ch[ido * (k + l1)]) = pm(cr2, ci3);
+    }
+    if ido == 1 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let tr2 = cc[i - 1 + ido * (2 + cdim * k)] + cc[ic - 1 + ido * (1 + cdim * k)];
+            let ti2 = cc[i + ido * (2 + cdim * k)] - cc[ic + ido * (1 + cdim * k)];
+            let cr2 = cc[i - 1 + ido * (cdim * k)] + taur * tr2;
+            let ci2 = cc[i + ido * (cdim * k)] + taur * ti2;
+
+            ch[i - 1 + ido * k] = cc[i - 1 + ido * (cdim * k)] + tr2;
+            ch[i + ido * k] = cc[i + ido * (cdim * k)] + ti2;
+
+            let cr3 = taui * (cc[i - 1 + ido * (2 + cdim * k)] - cc[ic - 1 + ido * (1 + cdim * k)]);
+            let ci3 = taui * (cc[i + ido * (2 + cdim * k)] + cc[ic + ido * (1 + cdim * k)]);
+            let (dr3, dr2) = pm(cr2, ci3);
+            let (di2, di3) = pm(ci2, cr3);
+
+            let d2 = wa[(i >> 1) - 1] * Complex::new(dr2, di2);
+            let d3 = wa[(i >> 1) - 1 + iw] * Complex::new(dr3, di3);
+            (ch[i - 1 + ido * (k + l1)], ch[i + ido * (k + l1)]) = (d2.re, d2.im);
+            (ch[i - 1 + ido * (k + l1 * 2)], ch[i + ido * (k + l1 * 2)]) = (d3.re, d3.im);
+        }
+    }
+}
+
+fn radb4<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 4;
+    let two = T::from_f64(2.0);
+    let iw = (ido - 1) >> 1;
+    let sqrt2 = T::from_f64(core::f64::consts::SQRT_2);
+
+    for k in 0..l1 {
+        let (tr2, tr1) = pm(cc[ido * (cdim * k)], cc[ido - 1 + ido * (3 + cdim * k)]);
+        let tr3 = two * cc[ido - 1 + ido * (1 + cdim * k)];
+        let tr4 = two * cc[ido * (2 + cdim * k)];
+        (ch[ido * k], ch[ido * (k + l1 * 2)]) = pm(tr2, tr3);
+        (ch[ido * (k + l1 * 3)], ch[ido * (k + l1)]) = pm(tr1, tr4);
+    }
+    if ido & 1 == 0 {
+        for k in 0..l1 {
+            let (ti1, ti2) = pm(cc[ido * (3 + cdim * k)], cc[ido * (1 + cdim * k)]);
+            let (tr2, tr1) = pm(cc[ido - 1 + ido * (cdim * k)], cc[ido - 1 + ido * (2 + cdim * k)]);
+            ch[ido - 1 + ido * k] = tr2 + tr2;
+            ch[ido - 1 + ido * (k + l1)] = sqrt2 * (tr1 - ti1);
+            ch[ido - 1 + ido * (k + l1 * 2)] = ti2 + ti2;
+            ch[ido - 1 + ido * (k + l1 * 3)] = -sqrt2 * (tr1 + ti1);
+        }
+    }
+    if ido <= 2 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let (tr2, tr1) = pm(cc[i - 1 + ido * (cdim * k)], cc[ic - 1 + ido * (3 + cdim * k)]);
+            let (ti1, ti2) = pm(cc[i + ido * (cdim * k)], cc[ic + ido * (3 + cdim * k)]);
+            let (tr4, ti3) = pm(cc[i + ido * (2 + cdim * k)], cc[ic + ido * (1 + cdim * k)]);
+            let (tr3, ti4) = pm(cc[i - 1 + ido * (2 + cdim * k)], cc[ic - 1 + ido * (1 + cdim * k)]);
+
+            let (cr3, ci3);
+            (ch[i - 1 + ido * k], cr3) = pm(tr2, tr3);
+            (ch[i + ido * k], ci3) = pm(ti2, ti3);
+            let (cr4, cr2) = pm(tr1, tr4);
+            let (ci2, ci4) = pm(ti1, ti4);
+
+            let c2 = wa[(i >> 1) - 1] * Complex::new(cr2, ci2);
+            let c3 = wa[(i >> 1) - 1 + iw] * Complex::new(cr3, ci3);
+            let c4 = wa[(i >> 1) - 1 + 2 * iw] * Complex::new(cr4, ci4);
+            (ch[i - 1 + ido * (k + l1)], ch[i + ido * (k + l1)]) = (c2.re, c2.im);
+            (ch[i - 1 + ido * (k + l1 * 2)], ch[i + ido * (k + l1 * 2)]) = (c3.re, c3.im);
+            (ch[i - 1 + ido * (k + l1 * 3)], ch[i + ido * (k + l1 * 3)]) = (c4.re, c4.im);
+        }
+    }
+}
+
+fn radb5<T: Float>(ido: usize, l1: usize, cc: &[T], ch: &mut [T], wa: &[Complex<T>]) {
+    let cdim = 5;
+    let two = T::from_f64(2.0);
+    let iw = (ido - 1) >> 1;
+    let tr11 = T::from_f64(0.30901699437494745);
+    let ti11 = T::from_f64(0.9510565162951535);
+    let tr12 = T::from_f64(-0.8090169943749475);
+    let ti12 = T::from_f64(0.5877852522924731);
+
+    for k in 0..l1 {
+        let ti5 = two * cc[ido * (2 + cdim * k)];
+        let ti4 = two * cc[ido * (4 + cdim * k)];
+        let tr2 = two * cc[ido - 1 + ido * (1 + cdim * k)];
+        let tr3 = two * cc[ido - 1 + ido * (3 + cdim * k)];
+        let c0 = cc[ido * (cdim * k)];
+
+        ch[ido * k] = c0 + tr2 + tr3;
+        let cr2 = c0 + tr11 * tr2 + tr12 * tr3;
+        let cr3 = c0 + tr12 * tr2 + tr11 * tr3;
+        let (ci5, ci4) = (ti5 * ti11 + ti4 * ti12, ti5 * ti12 - ti4 * ti11);
+        (ch[ido * (k + l1 * 4)], ch[ido * (k + l1)]) = pm(cr2, ci5);
+        (ch[ido * (k + l1 * 3)], ch[ido * (k + l1 * 2)]) = pm(cr3, ci4);
+    }
+    if ido == 1 { return; }
+
+    for k in 0..l1 {
+        for i in (2..ido).step_by(2) {
+            let ic = ido - i;
+            let (tr2, tr5) = pm(cc[i - 1 + ido * (2 + cdim * k)], cc[ic - 1 + ido * (1 + cdim * k)]);
+            let (ti5, ti2) = pm(cc[i + ido * (2 + cdim * k)], cc[ic + ido * (1 + cdim * k)]);
+            let (tr3, tr4) = pm(cc[i - 1 + ido * (4 + cdim * k)], cc[ic - 1 + ido * (3 + cdim * k)]);
+            let (ti4, ti3) = pm(cc[i + ido * (4 + cdim * k)], cc[ic + ido * (3 + cdim * k)]);
+            let c0 = Complex::new(cc[i - 1 + ido * (cdim * k)], cc[i + ido * (cdim * k)]);
+
+            ch[i - 1 + ido * k] = c0.re + tr2 + tr3;
+            ch[i + ido * k] = c0.im + ti2 + ti3;
+
+            let cr2 = c0.re + tr11 * tr2 + tr12 * tr3;
+            let ci2 = c0.im + tr11 * ti2 + tr12 * ti3;
+            let cr3 = c0.re + tr12 * tr2 + tr11 * tr3;
+            let ci3 = c0.im + tr12 * ti2 + tr11 * ti3;
+            let (cr5, cr4) = (tr5 * ti11 + tr4 * ti12, tr5 * ti12 - tr4 * ti11);
+            let (ci5, ci4) = (ti5 * ti11 + ti4 * ti12, ti5 * ti12 - ti4 * ti11);
+
+            let (dr4, dr3) = pm(cr3, ci4);
+            let (di3, di4) = pm(ci3, cr4);
+            let (dr5, dr2) = pm(cr2, ci5);
+            let (di2, di5) = pm(ci2, cr5);
+
+            let d2 = wa[(i >> 1) - 1] * Complex::new(dr2, di2);
+            let d3 = wa[(i >> 1) - 1 + iw] * Complex::new(dr3, di3);
+            let d4 = wa[(i >> 1) - 1 + 2 * iw] * Complex::new(dr4, di4);
+            let d5 = wa[(i >> 1) - 1 + 3 * iw] * Complex::new(dr5, di5);
+            (ch[i - 1 + ido * (k + l1)], ch[i + ido * (k + l1)]) = (d2.re, d2.im);
+            (ch[i - 1 + ido * (k + l1 * 2)], ch[i + ido * (k + l1 * 2)]) = (d3.re, d3.im);
+            (ch[i - 1 + ido * (k + l1 * 3)], ch[i + ido * (k + l1 * 3)]) = (d4.re, d4.im);
+            (ch[i - 1 + ido * (k + l1 * 4)], ch[i + ido * (k + l1 * 4)]) = (d5.re, d5.im);
+        }
+    }
+}
+
+fn radbg<T: Float>(ido: usize, ip: usize, l1: usize, cc: &mut [T], ch: &mut [T], wa: &[Complex<T>], csarr: &[Complex<T>]) {
+    let cdim = ip;
+    let two = T::from_f64(2.0);
+    let ipph = (ip + 1) >> 1;
+    let idl1 = ido * l1;
+    let iw = (ido - 1) >> 1;
+
+    for k in 0..l1 {
+        for i in 0..ido {
+            ch[i + ido * k] = cc[i + ido * (cdim * k)];
+        }
+    }
+
+    for j in 1..ipph {
+        let jc = ip - j;
+        let j2 = 2 * j - 1;
+        for k in 0..l1 {
+            ch[ido * (k + l1 * j)] = two * cc[ido - 1 + ido * (j2 + cdim * k)];
+            ch[ido * (k + l1 * jc)] = two * cc[ido * (j2 + 1 + cdim * k)];
+        }
+    }
+
+    if ido != 1 {
+        for j in 1..ipph {
+            let jc = ip - j;
+            let j2 = 2 * j - 1;
+            for k in 0..l1 {
+                for i in (1..ido - 1).step_by(2) {
+                    let ic = ido - i - 2;
+                    ch[i + ido * (k + l1 * j)] = cc[i + ido * (j2 + 1 + cdim * k)] + cc[ic + ido * (j2 + cdim * k)];
+                    ch[i + ido * (k + l1 * jc)] = cc[i + ido * (j2 + 1 + cdim * k)] - cc[ic + ido * (j2 + cdim * k)];
+                    ch[i + 1 + ido * (k + l1 * j)] = cc[i + 1 + ido * (j2 + 1 + cdim * k)] - cc[ic + 1 + ido * (j2 + cdim * k)];
+                    ch[i + 1 + ido * (k + l1 * jc)] = cc[i + 1 + ido * (j2 + 1 + cdim * k)] + cc[ic + 1 + ido * (j2 + cdim * k)];
+                }
+            }
+        }
+    }
+
+    for l in 1..ipph {
+        let lc = ip - l;
+
+        for ik in 0..idl1 {
+            cc[ik + idl1 * l] = ch[ik] + csarr[l].re * ch[ik + idl1] + csarr[2 * l].re * ch[ik + idl1 * 2];
+            cc[ik + idl1 * lc] = csarr[l].im * ch[ik + idl1 * (ip - 1)] + csarr[2 * l].im * ch[ik + idl1 * (ip - 2)];
+        }
+
+        let mut iang = 2 * l;
+        let mut j = 3;
+        let mut jc = ip - 3;
+
+        while j < ipph - 1 {
+            iang += l;
+            if iang >= ip { iang -= ip; }
+            let ar1 = csarr[iang];
+
+            iang += l;
+            if iang >= ip { iang -= ip; }
+            let ar2 = csarr[iang];
+
+            for ik in 0..idl1 {
+                cc[ik + idl1 * l] += ar1.re * ch[ik + idl1 * j] + ar2.re * ch[ik + idl1 * (j + 1)];
+                cc[ik + idl1 * lc] += ar1.im * ch[ik + idl1 * jc] + ar2.im * ch[ik + idl1 * (jc - 1)];
+            }
+            j += 2;
+            jc -= 2;
+        }
+
+        while j < ipph {
+            iang += l;
+            if iang >= ip { iang -= ip; }
+            let ar = csarr[iang];
+
+            for ik in 0..idl1 {
+                cc[ik + idl1 * l] += ar.re * ch[ik + idl1 * j];
+                cc[ik + idl1 * lc] += ar.im * ch[ik + idl1 * jc];
+            }
+            j += 1;
+            jc -= 1;
+        }
+    }
+
+    for j in 1..ipph {
+        for ik in 0..idl1 {
+            ch[ik] += ch[ik + idl1 * j];
+        }
+    }
+
+    for j in 1..ipph {
+        let jc = ip - j;
+        for k in 0..l1 {
+            ch[ido * (k + l1 * j)] = cc[ido * (k + l1 * j)] - cc[ido * (k + l1 * jc)];
+            ch[ido * (k + l1 * jc)] = cc[ido * (k + l1 * j)] + cc[ido * (k + l1 * jc)];
+        }
+    }
+
+    if ido == 1 { return; }
+
+    for j in 1..ipph {
+        let jc = ip - j;
+        for k in 0..l1 {
+            for i in (1..ido - 1).step_by(2) {
+                ch[i + ido * (k + l1 * j)] = cc[i + ido * (k + l1 * j)] - cc[i + 1 + ido * (k + l1 * jc)];
+                ch[i + ido * (k + l1 * jc)] = cc[i + ido * (k + l1 * j)] + cc[i + 1 + ido * (k + l1 * jc)];
+                ch[i + 1 + ido * (k + l1 * j)] = cc[i + 1 + ido * (k + l1 * j)] + cc[i + ido * (k + l1 * jc)];
+                ch[i + 1 + ido * (k + l1 * jc)] = cc[i + 1 + ido * (k + l1 * j)] - cc[i + ido * (k + l1 * jc)];
+            }
+        }
+    }
+
+    for j in 1..ip {
+        for k in 0..l1 {
+            for i in (1..ido - 1).step_by(2) {
+                let t = wa[(j - 1) * iw + (i >> 1)] * Complex::new(ch[i + ido * (k + l1 * j)], ch[i + 1 + ido * (k + l1 * j)]);
+                (ch[i + ido * (k + l1 * j)], ch[i + 1 + ido * (k + l1 * j)]) = (t.re, t.im);
+            }
+        }
+    }
+}
+
+pub struct RealCooleyTukey<T = f64> {
+    len: usize,
+    fct: Vec<FactorData<T>>
+}
+
+impl<T: Float> RealCooleyTukey<T> {
+    pub fn new(len: usize) -> RealCooleyTukey<T> {
+        let mut plan = RealCooleyTukey { len, fct: Vec::new() };
+        if len < 2 { return plan; }
+
+        plan.fct = factorize(len);
+        plan.compute_twiddle();
+        return plan;
+    }
+
+    // the odd radix passes only handle an odd ido, which is whatever the radices after them
+    // multiply to, so the 2s and 4s have to come first
+    pub fn with_factors(len: usize, factors: &[usize]) -> Result<RealCooleyTukey<T>> {
+        check_factors(len, factors)?;
+        if factors.windows(2).any(|w| w[0] % 2 == 1 && w[1] % 2 == 0) { return Err(FftError::InvalidParameter("real factors need the 2s and 4s ahead of the odd ones")); }
+        let mut plan = RealCooleyTukey { len, fct: Vec::new() };
+        if len < 2 { return Ok(plan); }
+
+        plan.fct = factors.iter().map(|&f| FactorData { fct: f, tw: Vec::new(), tws: Vec::new() }).collect();
+        plan.compute_twiddle();
+        return Ok(plan);
+    }
+
+    fn compute_twiddle(&mut self) {
+        let len = self.len;
+        let mut twid = alloc::vec![Complex::new(T::ZERO, T::ZERO); len];
+        sincos_2pibyn(len, &mut twid);
+
+        let mut l1 = 1;
+
+        for k in 0..self.fct.len() {
+            let ip = self.fct[k].fct;
+            let ido = len / (l1 * ip);
+            let iw = (ido - 1) >> 1;
+
+            if k < self.fct.len() - 1 {
+                self.fct[k].tw = alloc::vec![Complex::new(T::ZERO, T::ZERO); (ip - 1) * iw];
+                for j in 1..ip {
+                    for i in 1..=iw {
+                        self.fct[k].tw[(j - 1) * iw + i - 1] = twid[j * l1 * i];
+                    }
+                }
+            }
+
+            if ip > 5 {
+                self.fct[k].tws = alloc::vec![Complex::new(T::ZERO, T::ZERO); ip];
+                self.fct[k].tws[0] = Complex::new(T::ONE, T::ZERO);
+                for i in 1..=(ip >> 1) {
+                    self.fct[k].tws[i] = twid[i * (len / ip)];
+                    self.fct[k].tws[ip - i] = twid[i * (len / ip)].conj();
+                }
+            }
+
+            l1 *= ip;
+        }
+    }
+
+    pub fn forward(&self, input: &[T], output: &mut [Complex<T>], fct: T) -> Result {
+        check_len(self.len, input.len())?;
+        check_len(self.len / 2 + 1, output.len())?;
+        check_finite_real(input)?;
+        if self.len == 0 { output[0] = Complex::new(T::ZERO, T::ZERO); return Ok(()); }
+
+        let mut buf = input.to_vec();
+        self.rfftf(&mut buf, fct);
+
+        output[0] = Complex::new(buf[0], T::ZERO);
+        for k in 1..=(self.len - 1) / 2 { output[k] = Complex::new(buf[2 * k - 1], buf[2 * k]); }
+        if self.len & 1 == 0 { output[self.len / 2] = Complex::new(buf[self.len - 1], T::ZERO); }
+        return Ok(());
+    }
+
+    pub fn backward(&self, input: &[Complex<T>], output: &mut [T], fct: T) -> Result {
+        check_len(self.len / 2 + 1, input.len())?;
+        check_len(self.len, output.len())?;
+        check_finite(input)?;
+        if self.len == 0 { return Ok(()); }
+
+        output[0] = input[0].re;
+        for k in 1..=(self.len - 1) / 2 { (output[2 * k - 1], output[2 * k]) = (input[k].re, input[k].im); }
+        if self.len & 1 == 0 { output[self.len - 1] = input[self.len / 2].re; }
+
+        self.rfftb(output, fct);
+        return Ok(());
+    }
+
+    fn rfftf(&self, data: &mut [T], fct: T) {
+        if fct != T::ONE { data.iter_mut().for_each(|d| *d *= fct); }
+        if self.len < 2 { return; }
+
+        let mut l1 = self.len;
+        let mut ch = alloc::vec![T::ZERO; data.len()];
+        let (mut p1, mut p2) = (&mut data[..], &mut ch[..]);
+
+        for k1 in (0..self.fct.len()).rev() {
+            let ip = self.fct[k1].fct;
+            let ido = self.len / l1;
+            l1 /= ip;
+
+            match ip {
+                4 => radf4(ido, l1, p1, p2, &self.fct[k1].tw),
+                2 => radf2(ido, l1, p1, p2, &self.fct[k1].tw),
+                3 => radf3(ido, l1, p1, p2, &self.fct[k1].tw),
+                5 => radf5(ido, l1, p1, p2, &self.fct[k1].tw),
+                _ => { radfg(ido, ip, l1, p1, p2, &self.fct[k1].tw, &self.fct[k1].tws); (p1, p2) = (p2, p1); }
+            }
+
+            (p1, p2) = (p2, p1);
+        }
+        if p1.as_ptr() != data.as_ptr() { data.copy_from_slice(&ch); }
+    }
+
+    fn rfftb(&self, data: &mut [T], fct: T) {
+        if fct != T::ONE { data.iter_mut().for_each(|d| *d *= fct); }
+        if self.len < 2 { return; }
+
+        let mut l1 = 1;
+        let mut ch = alloc::vec![T::ZERO; data.len()];
+        let (mut p1, mut p2) = (&mut data[..], &mut ch[..]);
+
+        for k1 in 0..self.fct.len() {
+            let ip = self.fct[k1].fct;
+            let l2 = ip * l1;
+            let ido = self.len / l2;
+
+            match ip {
+                4 => radb4(ido, l1, p1, p2, &self.fct[k1].tw),
+                2 => radb2(ido, l1, p1, p2, &self.fct[k1].tw),
+                3 => radb3(ido, l1, p1, p2, &self.fct[k1].tw),
+                5 => radb5(ido, l1, p1, p2, &self.fct[k1].tw),
+                _ => radbg(ido, ip, l1, p1, p2, &self.fct[k1].tw, &self.fct[k1].tws)
+            }
+
+            (p1, p2, l1) = (p2, p1, l2);
+        }
+        if p1.as_ptr() != data.as_ptr() { data.copy_from_slice(&ch); }
+    }
+
+    pub fn len(&self) -> usize { self.len }
+
+    // real input is about half the work of a complex transform of the same length
+    pub fn describe(&self) -> PlanInfo {
+        return PlanInfo {
+            algorithm: Algorithm::Ct, len: self.len, real: true, factors: self.fct.iter().map(|f| f.fct).collect(), padded_len: None,
+            twiddle_bytes: table_bytes(&self.fct), flops: 0.5 * cost_guess(self.len), children: Vec::new()
+        };
+    }
+}
@@ -1,35 +1,34 @@
-use crate::ComplexExt;
+use crate::{ComplexExt, Float};
 use core::{
     fmt::{Debug, Display, Formatter, Result},
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign}
 };
-use libm::*;
 
 #[derive(Clone, Copy, PartialEq)]
-pub struct Complex { pub re: f64, pub im: f64 }
+pub struct Complex<T = f64> { pub re: T, pub im: T }
 
-impl Complex {
-    #[inline] pub fn new(re: f64, im: f64) -> Self { Self { re, im } }
-    #[inline] pub fn zero() -> Self { Self { re: 0.0, im: 0.0 } }
-    #[inline] pub fn one() -> Self { Self { re: 1.0, im: 0.0 } }
-    #[inline] pub fn i() -> Self { Self { re: 0.0, im: 1.0 } }
+impl<T: Float> Complex<T> {
+    #[inline] pub fn new(re: T, im: T) -> Self { Self { re, im } }
+    #[inline] pub fn zero() -> Self { Self { re: T::ZERO, im: T::ZERO } }
+    #[inline] pub fn one() -> Self { Self { re: T::ONE, im: T::ZERO } }
+    #[inline] pub fn i() -> Self { Self { re: T::ZERO, im: T::ONE } }
 
     #[inline] pub fn conj(&self) -> Self { Self { re: self.re, im: -self.im } } // conjugate
-    #[inline] pub fn dot(&self, rhs: Complex) -> f64 { self.re * rhs.re + self.im * rhs.im } // dot product
-    #[inline] pub fn norm_sqr(&self) -> f64 { self.dot(*self) } // complex norm squared
-    #[inline] pub fn norm(&self) -> f64 { sqrt(self.norm_sqr()) } // complex norm
-    #[inline] pub fn arg(&self) -> f64 { atan2(self.im, self.re) } // complex argument
+    #[inline] pub fn dot(&self, rhs: Complex<T>) -> T { self.re * rhs.re + self.im * rhs.im } // dot product
+    #[inline] pub fn norm_sqr(&self) -> T { self.dot(*self) } // complex norm squared
+    #[inline] pub fn norm(&self) -> T { self.norm_sqr().sqrt() } // complex norm
+    #[inline] pub fn arg(&self) -> T { self.im.atan2(self.re) } // complex argument
 
-    #[inline] pub fn to_polar(&self) -> (f64, f64) { (self.norm(), self.arg()) } // convert to polar form
-    #[inline] pub fn from_polar(re: f64, theta: f64) -> Self { Self { re: cos(theta), im: sin(theta) } * re } // convert from polar form
+    #[inline] pub fn to_polar(&self) -> (T, T) { (self.norm(), self.arg()) } // convert to polar form
+    #[inline] pub fn from_polar(re: T, theta: T) -> Self { Self { re: theta.cos(), im: theta.sin() } * re } // convert from polar form
 
-    #[inline] pub fn exp(&self) -> Self { Complex::from_polar(exp(self.re), self.im) } // complex natural exponentation
-    #[inline] pub fn ln(&self) -> Self { Self { re: log(self.norm()), im: self.arg() } } // complex natural logarithm
+    #[inline] pub fn exp(&self) -> Self { Complex::from_polar(self.re.exp(), self.im) } // complex natural exponentation
+    #[inline] pub fn ln(&self) -> Self { Self { re: self.norm().ln(), im: self.arg() } } // complex natural logarithm
 
-    #[inline] pub fn powf(&self, rhs: f64) -> Self { Complex::from_polar(pow(self.norm(), rhs), rhs * self.arg()) }
-    #[inline] pub fn powc(&self, rhs: Complex) -> Self { Complex::from_polar(exp(rhs.dot(self.ln().conj())), rhs.dot(self.ln().conj().rot90())) } 
-    #[inline] pub fn rootf(&self, rhs: f64) -> Self { self.powf(1.0 / rhs) }
-    #[inline] pub fn rootc(&self, rhs: Complex) -> Self { self.powc(1.0 / rhs) }
+    #[inline] pub fn powf(&self, rhs: T) -> Self { Complex::from_polar(self.norm().powf(rhs), rhs * self.arg()) }
+    #[inline] pub fn powc(&self, rhs: Complex<T>) -> Self { Complex::from_polar(rhs.dot(self.ln().conj()).exp(), rhs.dot(self.ln().conj().rot90())) }
+    #[inline] pub fn rootf(&self, rhs: T) -> Self { self.powf(T::ONE / rhs) }
+    #[inline] pub fn rootc(&self, rhs: Complex<T>) -> Self { self.powc(Complex::one() / rhs) }
 
     #[inline] pub fn is_nan(self) -> bool { self.re.is_nan() || self.im.is_nan() }
     #[inline] pub fn is_infinite(self) -> bool { !self.is_nan() && (self.re.is_infinite() || self.im.is_infinite()) }
@@ -38,52 +37,59 @@ impl Complex {
     #[inline] pub fn is_subnormal(self) -> bool { self.re.is_subnormal() || self.im.is_subnormal() }
 }
 
-impl Display for Complex {
+impl<T: Float> Display for Complex<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        match (self.re, self.im) {
-            (0.0, 0.0) => write!(f, "0"),
-            (0.0, _) => write!(f, "{}i", self.im),
-            (_, 0.0) => write!(f, "{}", self.re),
-            (_, _) => write!(f, "{}{}{}i", self.re, if self.im < 0.0 { "" } else { "+" }, self.im)
+        match (self.re == T::ZERO, self.im == T::ZERO) {
+            (true, true) => write!(f, "0"),
+            (true, _) => write!(f, "{}i", self.im),
+            (_, true) => write!(f, "{}", self.re),
+            (_, _) => write!(f, "{}{}{}i", self.re, if self.im < T::ZERO { "" } else { "+" }, self.im)
         }
     }
 }
 
-impl Debug for Complex {
+impl<T: Float> Debug for Complex<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        match (self.re, self.im) {
-            (0.0, 0.0) => write!(f, "0"),
-            (0.0, _) => write!(f, "{}i", self.im),
-            (_, 0.0) => write!(f, "{}", self.re),
-            (_, _) => write!(f, "{}{}{}i", self.re, if self.im < 0.0 { "" } else { "+" }, self.im)
+        match (self.re == T::ZERO, self.im == T::ZERO) {
+            (true, true) => write!(f, "0"),
+            (true, _) => write!(f, "{}i", self.im),
+            (_, true) => write!(f, "{}", self.re),
+            (_, _) => write!(f, "{}{}{}i", self.re, if self.im < T::ZERO { "" } else { "+" }, self.im)
         }
     }
 }
 
-impl Neg for Complex { type Output = Self; #[inline] fn neg(self) -> Self { Self { re: -self.re, im: -self.im } } }
-
-impl Add for Complex { type Output = Self; #[inline] fn add(self, rhs: Complex) -> Self { Self { re: self.re + rhs.re, im: self.im + rhs.im } } }
-impl Add<f64> for Complex { type Output = Self; #[inline] fn add(self, rhs: f64) -> Self { Self { re: self.re + rhs, im: self.im } } }
-impl Add<Complex> for f64 { type Output = Complex; #[inline] fn add(self, rhs: Complex) -> Complex { rhs + self } }
-impl AddAssign for Complex { #[inline] fn add_assign(&mut self, rhs: Complex) { *self = *self + rhs; } }
-impl AddAssign<f64> for Complex { #[inline] fn add_assign(&mut self, rhs: f64) { *self = *self + rhs; } }
-
-impl Sub for Complex { type Output = Self; #[inline] fn sub(self, rhs: Complex) -> Self { Self { re: self.re - rhs.re, im: self.im - rhs.im } } }
-impl Sub<f64> for Complex { type Output = Self; #[inline] fn sub(self, rhs: f64) -> Self { Self { re: self.re - rhs, im: self.im } } }
-impl Sub<Complex> for f64 { type Output = Complex; #[inline] fn sub(self, rhs: Complex) -> Complex { rhs - self } }
-impl SubAssign for Complex { #[inline] fn sub_assign(&mut self, rhs: Complex) { *self = *self - rhs; } }
-impl SubAssign<f64> for Complex { #[inline] fn sub_assign(&mut self, rhs: f64) { *self = *self - rhs; } }
-
-impl Mul for Complex { type Output = Self; #[inline] fn mul(self, rhs: Complex) -> Self { Self { re: self.dot(rhs.conj()), im: self.dot(rhs.rotm90().conj()) } } }
-impl Mul<f64> for Complex { type Output = Self; #[inline] fn mul(self, rhs: f64) -> Self { Self { re: self.re * rhs, im: self.im * rhs } } }
-impl Mul<Complex> for f64 { type Output = Complex; #[inline] fn mul(self, rhs: Complex) -> Complex { rhs * self } }
-impl MulAssign for Complex { #[inline] fn mul_assign(&mut self, rhs: Complex) { *self = *self * rhs; } }
-impl MulAssign<f64> for Complex { #[inline] fn mul_assign(&mut self, rhs: f64) { *self = *self * rhs; } }
-
-impl Div for Complex { type Output = Self; #[inline] fn div(self, rhs: Complex) -> Self { Self { re: self.dot(rhs), im: self.dot(rhs.rot90()) } / rhs.norm_sqr() } }
-impl Div<f64> for Complex { type Output = Self; #[inline] fn div(self, rhs: f64) -> Self { Self { re: self.re / rhs, im: self.im / rhs } } }
-impl Div<Complex> for f64 { type Output = Complex; #[inline] fn div(self, rhs: Complex) -> Complex { self * rhs.conj() / rhs.norm() } }
-impl DivAssign for Complex { #[inline] fn div_assign(&mut self, rhs: Complex) { *self = *self / rhs; } }
-impl DivAssign<f64> for Complex { #[inline] fn div_assign(&mut self, rhs: f64) { *self = *self / rhs; } }
-
-impl From<f64> for Complex { #[inline] fn from(re: f64) -> Self { Self { re, im: 0.0 } } }
\ No newline at end of file
+impl<T: Float> Neg for Complex<T> { type Output = Self; #[inline] fn neg(self) -> Self { Self { re: -self.re, im: -self.im } } }
+
+impl<T: Float> Add for Complex<T> { type Output = Self; #[inline] fn add(self, rhs: Complex<T>) -> Self { Self { re: self.re + rhs.re, im: self.im + rhs.im } } }
+impl<T: Float> Add<T> for Complex<T> { type Output = Self; #[inline] fn add(self, rhs: T) -> Self { Self { re: self.re + rhs, im: self.im } } }
+impl<T: Float> AddAssign for Complex<T> { #[inline] fn add_assign(&mut self, rhs: Complex<T>) { *self = *self + rhs; } }
+impl<T: Float> AddAssign<T> for Complex<T> { #[inline] fn add_assign(&mut self, rhs: T) { *self = *self + rhs; } }
+
+impl<T: Float> Sub for Complex<T> { type Output = Self; #[inline] fn sub(self, rhs: Complex<T>) -> Self { Self { re: self.re - rhs.re, im: self.im - rhs.im } } }
+impl<T: Float> Sub<T> for Complex<T> { type Output = Self; #[inline] fn sub(self, rhs: T) -> Self { Self { re: self.re - rhs, im: self.im } } }
+impl<T: Float> SubAssign for Complex<T> { #[inline] fn sub_assign(&mut self, rhs: Complex<T>) { *self = *self - rhs; } }
+impl<T: Float> SubAssign<T> for Complex<T> { #[inline] fn sub_assign(&mut self, rhs: T) { *self = *self - rhs; } }
+
+impl<T: Float> Mul for Complex<T> { type Output = Self; #[inline] fn mul(self, rhs: Complex<T>) -> Self { Self { re: self.dot(rhs.conj()), im: self.dot(rhs.rotm90().conj()) } } }
+impl<T: Float> Mul<T> for Complex<T> { type Output = Self; #[inline] fn mul(self, rhs: T) -> Self { Self { re: self.re * rhs, im: self.im * rhs } } }
+impl<T: Float> MulAssign for Complex<T> { #[inline] fn mul_assign(&mut self, rhs: Complex<T>) { *self = *self * rhs; } }
+impl<T: Float> MulAssign<T> for Complex<T> { #[inline] fn mul_assign(&mut self, rhs: T) { *self = *self * rhs; } }
+
+impl<T: Float> Div for Complex<T> { type Output = Self; #[inline] fn div(self, rhs: Complex<T>) -> Self { Self { re: self.dot(rhs), im: self.dot(rhs.rot90()) } / rhs.norm_sqr() } }
+impl<T: Float> Div<T> for Complex<T> { type Output = Self; #[inline] fn div(self, rhs: T) -> Self { Self { re: self.re / rhs, im: self.im / rhs } } }
+impl<T: Float> DivAssign for Complex<T> { #[inline] fn div_assign(&mut self, rhs: Complex<T>) { *self = *self / rhs; } }
+impl<T: Float> DivAssign<T> for Complex<T> { #[inline] fn div_assign(&mut self, rhs: T) { *self = *self / rhs; } }
+
+impl<T: Float> From<T> for Complex<T> { #[inline] fn from(re: T) -> Self { Self { re, im: T::ZERO } } }
+
+macro_rules! impl_scalar_lhs {
+    ($($t:ty),*) => {$(
+        impl Add<Complex<$t>> for $t { type Output = Complex<$t>; #[inline] fn add(self, rhs: Complex<$t>) -> Complex<$t> { rhs + self } }
+        impl Sub<Complex<$t>> for $t { type Output = Complex<$t>; #[inline] fn sub(self, rhs: Complex<$t>) -> Complex<$t> { rhs - self } }
+        impl Mul<Complex<$t>> for $t { type Output = Complex<$t>; #[inline] fn mul(self, rhs: Complex<$t>) -> Complex<$t> { rhs * self } }
+        impl Div<Complex<$t>> for $t { type Output = Complex<$t>; #[inline] fn div(self, rhs: Complex<$t>) -> Complex<$t> { self * rhs.conj() / rhs.norm() } }
+    )*};
+}
+
+impl_scalar_lhs!(f32, f64);
@@ -0,0 +1,260 @@
+use crate::{
+    math::{cost_guess, good_size},
+    CfftPlan, Complex, FftError, Float, Norm, Result, RfftPlan
+};
+
+use alloc::vec::Vec;
+
+// same meaning as numpy.convolve's `mode`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConvMode {
+    #[default]
+    Full, // every overlap, a.len() + b.len() - 1 points
+    Same, // max(a.len(), b.len()) points, centered on the full result
+    Valid // only where the shorter input fully overlaps the longer one
+}
+
+impl ConvMode {
+    // where the requested part starts in the full result and how long it is
+    fn span(self, na: usize, nb: usize) -> (usize, usize) {
+        let (short, long) = if na < nb { (na, nb) } else { (nb, na) };
+        match self {
+            Self::Full => (0, na + nb - 1),
+            Self::Same => ((short - 1) / 2, long),
+            Self::Valid => (short - 1, long - short + 1)
+        }
+    }
+}
+
+// keeps the plans around between calls, handy when convolving block after block of
+// similar sizes. the free functions below build a throwaway one each time
+pub struct Convolver<T = f64> {
+    real: Vec<RfftPlan<T>>,
+    complex: Vec<CfftPlan<T>>
+}
+
+impl<T: Float> Default for Convolver<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T: Float> Convolver<T> {
+    pub fn new() -> Self { Convolver { real: Vec::new(), complex: Vec::new() } }
+
+    pub fn convolve(&mut self, a: &[T], b: &[T], mode: ConvMode) -> Result<Vec<T>> {
+        check_inputs(a, b)?;
+        let full = if direct_wins(a.len(), b.len()) { direct(a, b, T::ZERO) } else { self.fft_real(a, b)? };
+        return Ok(extract(full, mode, a.len(), b.len()));
+    }
+
+    pub fn correlate(&mut self, a: &[T], v: &[T], mode: ConvMode) -> Result<Vec<T>> {
+        let rev: Vec<T> = v.iter().rev().copied().collect();
+        return self.convolve(a, &rev, mode);
+    }
+
+    pub fn autocorrelate(&mut self, a: &[T], mode: ConvMode) -> Result<Vec<T>> {
+        check_inputs(a, a)?;
+        let n = a.len();
+        if direct_wins(n, n) { return self.correlate(a, a, mode); }
+
+        // one forward transform is enough, the spectrum times its own conjugate
+        let l = good_size(2 * n - 1);
+        let plan = plan_for(&mut self.real, l);
+        let mut buf = alloc::vec![T::ZERO; l];
+        let mut spec = alloc::vec![Complex::new(T::ZERO, T::ZERO); l / 2 + 1];
+        buf[..n].copy_from_slice(a);
+        plan.forward(&buf, &mut spec, Norm::Backward)?;
+        spec.iter_mut().for_each(|s| *s = Complex::new(s.re * s.re + s.im * s.im, T::ZERO));
+        plan.backward(&spec, &mut buf, Norm::Backward)?;
+
+        let full = (0..2 * n - 1).map(|i| buf[(i + l + 1 - n) % l]).collect();
+        return Ok(extract(full, mode, n, n));
+    }
+
+    pub fn convolve_complex(&mut self, a: &[Complex<T>], b: &[Complex<T>], mode: ConvMode) -> Result<Vec<Complex<T>>> {
+        check_inputs(a, b)?;
+        let zero = Complex::new(T::ZERO, T::ZERO);
+        let full = if direct_wins(a.len(), b.len()) { direct(a, b, zero) } else { self.fft_complex(a, b)? };
+        return Ok(extract(full, mode, a.len(), b.len()));
+    }
+
+    pub fn correlate_complex(&mut self, a: &[Complex<T>], v: &[Complex<T>], mode: ConvMode) -> Result<Vec<Complex<T>>> {
+        let rev: Vec<Complex<T>> = v.iter().rev().map(|x| x.conj()).collect();
+        return self.convolve_complex(a, &rev, mode);
+    }
+
+    pub fn autocorrelate_complex(&mut self, a: &[Complex<T>], mode: ConvMode) -> Result<Vec<Complex<T>>> {
+        check_inputs(a, a)?;
+        let n = a.len();
+        if direct_wins(n, n) { return self.correlate_complex(a, a, mode); }
+
+        let l = good_size(2 * n - 1);
+        let plan = plan_for(&mut self.complex, l);
+        let mut buf = alloc::vec![Complex::new(T::ZERO, T::ZERO); l];
+        buf[..n].copy_from_slice(a);
+        plan.forward(&mut buf, Norm::Backward)?;
+        buf.iter_mut().for_each(|s| *s = Complex::new(s.re * s.re + s.im * s.im, T::ZERO));
+        plan.backward(&mut buf, Norm::Backward)?;
+
+        let full = (0..2 * n - 1).map(|i| buf[(i + l + 1 - n) % l]).collect();
+        return Ok(extract(full, mode, n, n));
+    }
+
+    fn fft_real(&mut self, a: &[T], b: &[T]) -> Result<Vec<T>> {
+        let n = a.len() + b.len() - 1;
+        let l = good_size(n);
+        let plan = plan_for(&mut self.real, l);
+
+        let mut buf = alloc::vec![T::ZERO; l];
+        let mut fa = alloc::vec![Complex::new(T::ZERO, T::ZERO); l / 2 + 1];
+        let mut fb = fa.clone();
+        buf[..a.len()].copy_from_slice(a);
+        plan.forward(&buf, &mut fa, Norm::Backward)?;
+        buf.fill(T::ZERO);
+        buf[..b.len()].copy_from_slice(b);
+        plan.forward(&buf, &mut fb, Norm::Backward)?;
+
+        for (x, &y) in fa.iter_mut().zip(fb.iter()) { *x *= y; }
+        plan.backward(&fa, &mut buf, Norm::Backward)?;
+        buf.truncate(n);
+        return Ok(buf);
+    }
+
+    fn fft_complex(&mut self, a: &[Complex<T>], b: &[Complex<T>]) -> Result<Vec<Complex<T>>> {
+        let n = a.len() + b.len() - 1;
+        let l = good_size(n);
+        let plan = plan_for(&mut self.complex, l);
+
+        let mut fa = alloc::vec![Complex::new(T::ZERO, T::ZERO); l];
+        let mut fb = fa.clone();
+        fa[..a.len()].copy_from_slice(a);
+        fb[..b.len()].copy_from_slice(b);
+        plan.forward(&mut fa, Norm::Backward)?;
+        plan.forward(&mut fb, Norm::Backward)?;
+
+        for (x, &y) in fa.iter_mut().zip(fb.iter()) { *x *= y; }
+        plan.backward(&mut fa, Norm::Backward)?;
+        fa.truncate(n);
+        return Ok(fa);
+    }
+}
+
+trait Plan { fn new(n: usize) -> Self; fn len(&self) -> usize; }
+impl<T: Float> Plan for RfftPlan<T> { fn new(n: usize) -> Self { RfftPlan::new(n) } fn len(&self) -> usize { self.len() } }
+impl<T: Float> Plan for CfftPlan<T> { fn new(n: usize) -> Self { CfftPlan::new(n) } fn len(&self) -> usize { self.len() } }
+
+fn plan_for<P: Plan>(cache: &mut Vec<P>, n: usize) -> &P {
+    let idx = match cache.iter().position(|p| p.len() == n) {
+        Some(idx) => idx,
+        None => { cache.push(P::new(n)); cache.len() - 1 }
+    };
+    return &cache[idx];
+}
+
+fn check_inputs<X>(a: &[X], b: &[X]) -> Result {
+    if a.is_empty() || b.is_empty() { return Err(FftError::UnsupportedSize(0)); }
+    return Ok(());
+}
+
+// three transforms of the padded length against one multiply-add per pair of samples
+fn direct_wins(na: usize, nb: usize) -> bool {
+    return ((na * nb) as f64) < 3.0 * cost_guess(good_size(na + nb - 1));
+}
+
+fn direct<X: Copy + core::ops::Mul<Output = X> + core::ops::AddAssign>(a: &[X], b: &[X], zero: X) -> Vec<X> {
+    let mut out = alloc::vec![zero; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() { out[i + j] += x * y; }
+    }
+    return out;
+}
+
+fn extract<X>(mut full: Vec<X>, mode: ConvMode, na: usize, nb: usize) -> Vec<X> {
+    let (start, len) = mode.span(na, nb);
+    full.truncate(start + len);
+    full.drain(..start);
+    return full;
+}
+
+pub fn convolve<T: Float>(a: &[T], b: &[T], mode: ConvMode) -> Result<Vec<T>> { Convolver::new().convolve(a, b, mode) }
+pub fn correlate<T: Float>(a: &[T], v: &[T], mode: ConvMode) -> Result<Vec<T>> { Convolver::new().correlate(a, v, mode) }
+pub fn autocorrelate<T: Float>(a: &[T], mode: ConvMode) -> Result<Vec<T>> { Convolver::new().autocorrelate(a, mode) }
+
+pub fn convolve_complex<T: Float>(a: &[Complex<T>], b: &[Complex<T>], mode: ConvMode) -> Result<Vec<Complex<T>>> { Convolver::new().convolve_complex(a, b, mode) }
+pub fn correlate_complex<T: Float>(a: &[Complex<T>], v: &[Complex<T>], mode: ConvMode) -> Result<Vec<Complex<T>>> { Convolver::new().correlate_complex(a, v, mode) }
+pub fn autocorrelate_complex<T: Float>(a: &[Complex<T>], mode: ConvMode) -> Result<Vec<Complex<T>>> { Convolver::new().autocorrelate_complex(a, mode) }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{complex_signal, err, err_real, real_to_complex, signal};
+
+    // both sides of direct_wins
+    const SIZES: [(usize, usize); 6] = [(1, 1), (5, 3), (3, 8), (64, 7), (200, 150), (513, 1000)];
+
+    // the full sum over every pair, out[k] = sum of a[i] b[k - i]
+    fn sum_conv(a: &[f64], b: &[f64]) -> Vec<f64> {
+        return (0..a.len() + b.len() - 1).map(|k| (0..a.len()).filter(|&i| k >= i && k - i < b.len()).map(|i| a[i] * b[k - i]).sum()).collect();
+    }
+
+    #[test]
+    fn convolve_matches_sum() {
+        for (na, nb) in SIZES {
+            let (a, b) = (signal(na, 1), signal(nb, 2));
+            let full = sum_conv(&a, &b);
+            assert!(err_real(&convolve(&a, &b, ConvMode::Full).unwrap(), &full) < 1e-13, "{} {}", na, nb);
+
+            let (short, long) = (na.min(nb), na.max(nb));
+            let same = convolve(&a, &b, ConvMode::Same).unwrap();
+            assert_eq!(same.len(), long);
+            assert!(err_real(&same, &full[(short - 1) / 2..][..long]) < 1e-13);
+            let valid = convolve(&a, &b, ConvMode::Valid).unwrap();
+            assert_eq!(valid.len(), long - short + 1);
+            assert!(err_real(&valid, &full[short - 1..][..long - short + 1]) < 1e-13);
+        }
+    }
+
+    // numpy.correlate: out at lag k is the sum of a[n + k] conj(v[n]), lags from -(nv - 1) up
+    #[test]
+    fn correlate_matches_lag_sums() {
+        for (na, nv) in SIZES {
+            let (a, v) = (complex_signal(na, 3), complex_signal(nv, 4));
+            let want: Vec<Complex> = (0..na + nv - 1).map(|i| {
+                let lag = i as isize - (nv as isize - 1);
+                (0..nv).filter(|&n| (0..na as isize).contains(&(n as isize + lag)))
+                    .fold(Complex::new(0.0, 0.0), |acc, n| acc + a[(n as isize + lag) as usize] * v[n].conj())
+            }).collect();
+            assert!(err(&correlate_complex(&a, &v, ConvMode::Full).unwrap(), &want) < 1e-13, "{} {}", na, nv);
+
+            let (ar, vr): (Vec<f64>, Vec<f64>) = (a.iter().map(|c| c.re).collect(), v.iter().map(|c| c.re).collect());
+            let want: Vec<Complex> = correlate_complex(&real_to_complex(&ar), &real_to_complex(&vr), ConvMode::Full).unwrap();
+            let want: Vec<f64> = want.iter().map(|c| c.re).collect();
+            assert!(err_real(&correlate(&ar, &vr, ConvMode::Full).unwrap(), &want) < 1e-13);
+        }
+    }
+
+    // the single transform shortcut gives what correlating with itself would
+    #[test]
+    fn autocorrelate_matches_correlate() {
+        for n in [1, 4, 30, 300, 1000] {
+            let a = signal(n, 5);
+            for mode in [ConvMode::Full, ConvMode::Same, ConvMode::Valid] {
+                assert!(err_real(&autocorrelate(&a, mode).unwrap(), &correlate(&a, &a, mode).unwrap()) < 1e-13, "{} {:?}", n, mode);
+            }
+            let c = complex_signal(n, 6);
+            assert!(err(&autocorrelate_complex(&c, ConvMode::Full).unwrap(), &correlate_complex(&c, &c, ConvMode::Full).unwrap()) < 1e-13);
+        }
+    }
+
+    #[test]
+    fn complex_and_cached() {
+        let mut conv = Convolver::<f64>::new();
+        for _ in 0..2 {
+            let (a, b) = (complex_signal(300, 7), complex_signal(120, 8));
+            let want = direct(&a, &b, Complex::new(0.0, 0.0));
+            assert!(err(&conv.convolve_complex(&a, &b, ConvMode::Full).unwrap(), &want) < 1e-13);
+        }
+        assert_eq!(conv.complex.len(), 1);
+        assert_eq!(convolve::<f64>(&[], &[1.0], ConvMode::Full), Err(FftError::UnsupportedSize(0)));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Complex, Float, Result};
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FftError {
+    LengthMismatch { expected: usize, actual: usize },
+    UnsupportedSize(usize),
+    ScratchTooSmall { expected: usize, actual: usize },
+    NonFiniteInput(usize), // index of the first offender, only with the `validate` feature
+    InvalidAxis { axis: usize, ndim: usize },
+    InvalidParameter(&'static str) // what was wrong with it
+}
+
+impl Display for FftError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::LengthMismatch { expected, actual } => write!(f, "length mismatch: expected {}, got {}", expected, actual),
+            Self::UnsupportedSize(n) => write!(f, "unsupported size: {}", n),
+            Self::ScratchTooSmall { expected, actual } => write!(f, "scratch buffer too small: expected at least {}, got {}", expected, actual),
+            Self::NonFiniteInput(idx) => write!(f, "non-finite input at index {}", idx),
+            Self::InvalidAxis { axis, ndim } => write!(f, "axis {} out of range for {} dimensions", axis, ndim),
+            Self::InvalidParameter(what) => write!(f, "invalid parameter: {}", what)
+        }
+    }
+}
+
+impl core::error::Error for FftError {}
+
+#[inline]
+pub fn check_len(expected: usize, actual: usize) -> Result {
+    if expected != actual { return Err(FftError::LengthMismatch { expected, actual }); }
+    return Ok(());
+}
+
+#[inline]
+pub fn check_scratch(expected: usize, actual: usize) -> Result {
+    if actual < expected { return Err(FftError::ScratchTooSmall { expected, actual }); }
+    return Ok(());
+}
+
+#[inline]
+pub fn check_finite<T: Float>(data: &[Complex<T>]) -> Result {
+    if !cfg!(feature = "validate") { return Ok(()); }
+    match data.iter().position(|d| !(d.re.is_finite() && d.im.is_finite())) {
+        Some(idx) => Err(FftError::NonFiniteInput(idx)),
+        None => Ok(())
+    }
+}
+
+#[inline]
+pub fn check_finite_real<T: Float>(data: &[T]) -> Result {
+    if !cfg!(feature = "validate") { return Ok(()); }
+    match data.iter().position(|d| !d.is_finite()) {
+        Some(idx) => Err(FftError::NonFiniteInput(idx)),
+        None => Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CfftPlan, Norm, RfftPlan};
+    use alloc::string::ToString;
+
+    #[test]
+    fn messages() {
+        assert_eq!(FftError::LengthMismatch { expected: 4, actual: 3 }.to_string(), "length mismatch: expected 4, got 3");
+        assert_eq!(FftError::InvalidAxis { axis: 2, ndim: 2 }.to_string(), "axis 2 out of range for 2 dimensions");
+        assert_eq!(FftError::InvalidParameter("hop must be positive").to_string(), "invalid parameter: hop must be positive");
+    }
+
+    #[test]
+    fn non_finite_input() {
+        let mut data = alloc::vec![Complex::new(1.0, 0.0); 8];
+        data[5].im = f64::NAN;
+        let res = CfftPlan::<f64>::new(8).forward(&mut data, Norm::Backward);
+        if cfg!(feature = "validate") { assert_eq!(res, Err(FftError::NonFiniteInput(5))); } else { assert!(res.is_ok()); }
+
+        let mut out = alloc::vec![Complex::new(0.0, 0.0); 5];
+        let res = RfftPlan::<f64>::new(8).forward(&[0.0, 1.0, f64::INFINITY, 0.0, 0.0, 0.0, 0.0, 0.0], &mut out, Norm::Backward);
+        if cfg!(feature = "validate") { assert_eq!(res, Err(FftError::NonFiniteInput(2))); } else { assert!(res.is_ok()); }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{
+    error::check_len,
+    math::good_size, Complex, FftError, Float, Norm, Result, RfftPlan
+};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FirMethod {
+    #[default]
+    OverlapSave,
+    OverlapAdd
+}
+
+// one uniformly partitioned convolver. the kernel is cut into pieces of `part` samples,
+// every piece gets its own spectrum and the input spectra of the last few blocks sit in
+// a frequency domain delay line, so a block costs one forward and one inverse transform
+// no matter how many pieces there are
+struct Stage<T> {
+    block: usize,
+    method: FirMethod,
+    plan: RfftPlan<T>,
+    kernel: Vec<Vec<Complex<T>>>,
+    fdl: Vec<Vec<Complex<T>>>,
+    head: usize,
+    sum: Vec<Complex<T>>,
+    input: Vec<T>, // sliding window for overlap-save, zero padded block for overlap-add
+    buf: Vec<T>,
+    tail: Vec<T>, // overlap-add only
+    output: Vec<T>,
+    pos: usize
+}
+
+impl<T: Float> Stage<T> {
+    fn new(kernel: &[T], block: usize, part: usize, method: FirMethod) -> Result<Self> {
+        let n = good_size(block + part - 1);
+        let plan = RfftPlan::new(n);
+        let zero = Complex::new(T::ZERO, T::ZERO);
+
+        let mut buf = alloc::vec![T::ZERO; n];
+        let mut spectra = Vec::new();
+        for piece in kernel.chunks(part) {
+            let mut spec = alloc::vec![zero; n / 2 + 1];
+            buf.fill(T::ZERO);
+            buf[..piece.len()].copy_from_slice(piece);
+            plan.forward(&buf, &mut spec, Norm::Backward)?;
+            spectra.push(spec);
+        }
+
+        let parts = spectra.len();
+        return Ok(Stage {
+            block, method, plan,
+            kernel: spectra,
+            fdl: alloc::vec![alloc::vec![zero; n / 2 + 1]; parts],
+            head: 0,
+            sum: alloc::vec![zero; n / 2 + 1],
+            input: alloc::vec![T::ZERO; n],
+            buf,
+            tail: alloc::vec![T::ZERO; if method == FirMethod::OverlapAdd { n } else { 0 }],
+            output: alloc::vec![T::ZERO; block],
+            pos: 0
+        });
+    }
+
+    // adds this stage's share into output, which runs one block behind the input
+    fn process(&mut self, input: &[T], output: &mut [T]) -> Result {
+        let n = self.input.len();
+        let offset = if self.method == FirMethod::OverlapSave { n - self.block } else { 0 };
+        let mut i = 0;
+
+        while i < input.len() {
+            let take = (self.block - self.pos).min(input.len() - i);
+            for j in 0..take {
+                output[i + j] += self.output[self.pos + j];
+                self.input[offset + self.pos + j] = input[i + j];
+            }
+            self.pos += take;
+            i += take;
+
+            if self.pos == self.block {
+                self.run_block()?;
+                self.pos = 0;
+            }
+        }
+        return Ok(());
+    }
+
+    fn run_block(&mut self) -> Result {
+        let (n, parts) = (self.input.len(), self.kernel.len());
+        self.plan.forward(&self.input, &mut self.fdl[self.head], Norm::Backward)?;
+
+        self.sum.fill(Complex::new(T::ZERO, T::ZERO));
+        for p in 0..parts {
+            let x = &self.fdl[(self.head + parts - p) % parts];
+            for (s, (&h, &x)) in self.sum.iter_mut().zip(self.kernel[p].iter().zip(x.iter())) { *s += h * x; }
+        }
+        self.head = (self.head + 1) % parts;
+        self.plan.backward(&self.sum, &mut self.buf, Norm::Backward)?;
+
+        match self.method {
+            FirMethod::OverlapSave => {
+                self.output.copy_from_slice(&self.buf[n - self.block..]);
+                self.input.copy_within(self.block.., 0);
+            }
+            FirMethod::OverlapAdd => {
+                for (t, &y) in self.tail.iter_mut().zip(self.buf.iter()) { *t += y; }
+                self.output.copy_from_slice(&self.tail[..self.block]);
+                self.tail.copy_within(self.block.., 0);
+                self.tail[n - self.block..].fill(T::ZERO);
+            }
+        }
+        return Ok(());
+    }
+
+    fn reset(&mut self) {
+        let zero = Complex::new(T::ZERO, T::ZERO);
+        self.fdl.iter_mut().for_each(|x| x.fill(zero));
+        self.input.fill(T::ZERO);
+        self.tail.fill(T::ZERO);
+        self.output.fill(T::ZERO);
+        (self.head, self.pos) = (0, 0);
+    }
+}
+
+// streaming fir filter, feed it chunks of any size and get the same amount back,
+// delayed by latency() samples
+pub struct FirFilter<T = f64> {
+    stages: Vec<Stage<T>>,
+    latency: usize,
+    len: usize
+}
+
+impl<T: Float> FirFilter<T> {
+    // classic single transform per block, sized for the whole kernel
+    pub fn new(kernel: &[T], block: usize, method: FirMethod) -> Result<Self> {
+        if kernel.is_empty() || block == 0 { return Err(FftError::UnsupportedSize(0)); }
+        let stage = Stage::new(kernel, block, kernel.len(), method)?;
+        return Ok(FirFilter { stages: alloc::vec![stage], latency: block, len: kernel.len() });
+    }
+
+    // kernel cut into block sized pieces, transforms stay small however long the kernel is
+    pub fn uniform(kernel: &[T], block: usize, method: FirMethod) -> Result<Self> {
+        if kernel.is_empty() || block == 0 { return Err(FftError::UnsupportedSize(0)); }
+        let stage = Stage::new(kernel, block, block, method)?;
+        return Ok(FirFilter { stages: alloc::vec![stage], latency: block, len: kernel.len() });
+    }
+
+    // gardner style: the head of the kernel runs with the smallest block, later parts with
+    // bigger ones. stage s picks up at blocks[s] - blocks[0] samples into the kernel, which is
+    // exactly when its own extra latency stops mattering, so the whole thing still only lags
+    // by blocks[0]. blocks must be strictly increasing
+    pub fn non_uniform(kernel: &[T], blocks: &[usize]) -> Result<Self> {
+        if kernel.is_empty() || blocks.is_empty() || blocks[0] == 0 { return Err(FftError::UnsupportedSize(0)); }
+        if let Some(w) = blocks.windows(2).find(|w| w[1] <= w[0]) { return Err(FftError::UnsupportedSize(w[1])); }
+
+        let mut stages = Vec::new();
+        for (s, &block) in blocks.iter().enumerate() {
+            let start = block - blocks[0];
+            if start >= kernel.len() { break; }
+            let end = match blocks.get(s + 1) {
+                Some(&next) => (next - blocks[0]).min(kernel.len()),
+                None => kernel.len()
+            };
+            stages.push(Stage::new(&kernel[start..end], block, block, FirMethod::OverlapSave)?);
+        }
+        return Ok(FirFilter { stages, latency: blocks[0], len: kernel.len() });
+    }
+
+    pub fn process(&mut self, input: &[T], output: &mut [T]) -> Result {
+        check_len(input.len(), output.len())?;
+        output.fill(T::ZERO);
+        for stage in self.stages.iter_mut() { stage.process(input, output)?; }
+        return Ok(());
+    }
+
+    pub fn reset(&mut self) { self.stages.iter_mut().for_each(|s| s.reset()); }
+
+    pub fn latency(&self) -> usize { self.latency }
+    pub fn len(&self) -> usize { self.len }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{err_real, signal};
+
+    // streams x through in ragged chunks, then checks it against the direct convolution
+    // pushed back by the latency
+    fn check(filter: &mut FirFilter<f64>, kernel: &[f64], x: &[f64]) {
+        let mut out = alloc::vec![0.0; x.len()];
+        let (mut i, mut step) = (0, 1);
+        while i < x.len() {
+            let end = (i + step).min(x.len());
+            filter.process(&x[i..end], &mut out[i..end]).unwrap();
+            (i, step) = (end, step % 37 + 7);
+        }
+        let lat = filter.latency();
+        let want: Vec<f64> = (0..x.len() - lat).map(|k| (0..kernel.len()).filter(|&j| j <= k).map(|j| kernel[j] * x[k - j]).sum()).collect();
+        assert!(out[..lat].iter().all(|&v| v == 0.0));
+        assert!(err_real(&out[lat..], &want) < 1e-12);
+    }
+
+    #[test]
+    fn single_and_uniform_partitions() {
+        let x = signal(1200, 1);
+        for klen in [1, 5, 64, 300] {
+            let kernel = signal(klen, klen as u64);
+            for method in [FirMethod::OverlapSave, FirMethod::OverlapAdd] {
+                for block in [1, 16, 100] {
+                    check(&mut FirFilter::new(&kernel, block, method).unwrap(), &kernel, &x);
+                    check(&mut FirFilter::uniform(&kernel, block, method).unwrap(), &kernel, &x);
+                }
+            }
+        }
+    }
+
+    // kernels that end inside, on and past each stage boundary
+    #[test]
+    fn non_uniform_partitions() {
+        let x = signal(2000, 2);
+        for klen in [10, 16, 17, 48, 49, 112, 113, 500, 1500] {
+            let kernel = signal(klen, klen as u64);
+            check(&mut FirFilter::non_uniform(&kernel, &[16, 32, 64, 128]).unwrap(), &kernel, &x);
+            check(&mut FirFilter::non_uniform(&kernel, &[16]).unwrap(), &kernel, &x);
+        }
+    }
+
+    #[test]
+    fn reset_starts_over() {
+        let (kernel, x) = (signal(200, 3), signal(1000, 4));
+        let mut filter = FirFilter::non_uniform(&kernel, &[32, 64]).unwrap();
+        check(&mut filter, &kernel, &x);
+        filter.reset();
+        check(&mut filter, &kernel, &x);
+    }
+
+    #[test]
+    fn bad_setups() {
+        assert!(FirFilter::<f64>::new(&[], 16, FirMethod::OverlapSave).is_err());
+        assert!(FirFilter::<f64>::uniform(&[1.0], 0, FirMethod::OverlapAdd).is_err());
+        assert_eq!(FirFilter::<f64>::non_uniform(&[1.0; 8], &[16, 16]).err(), Some(FftError::UnsupportedSize(16)));
+        let mut filter = FirFilter::<f64>::new(&[1.0; 8], 16, FirMethod::OverlapSave).unwrap();
+        assert_eq!(filter.process(&[0.0; 4], &mut [0.0; 3]), Err(FftError::LengthMismatch { expected: 4, actual: 3 }));
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "simd")]
+use crate::Complex;
+use core::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign}
+};
+
+#[cfg(feature = "num-complex")]
+pub trait FloatBase: num_traits::NumAssign {}
+#[cfg(not(feature = "num-complex"))]
+pub trait FloatBase {}
+
+impl FloatBase for f32 {}
+impl FloatBase for f64 {}
+
+pub trait Float:
+    FloatBase + Copy + Default + Send + Sync + PartialEq + PartialOrd + Debug + Display +
+    Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self> +
+    AddAssign + SubAssign + MulAssign + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const EPSILON: Self;
+
+    fn from_f64(x: f64) -> Self; // rounds to nearest
+    fn to_f64(self) -> f64;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, rhs: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan2(self, rhs: Self) -> Self;
+
+    fn is_nan(self) -> bool;
+    fn is_infinite(self) -> bool;
+    fn is_finite(self) -> bool;
+    fn is_normal(self) -> bool;
+    fn is_subnormal(self) -> bool;
+
+    // hands a fixed radix pass to the simd kernels, false means do it in scalar code
+    #[cfg(feature = "simd")]
+    #[allow(clippy::too_many_arguments)]
+    fn simd_pass(_ip: usize, _ido: usize, _l1: usize, _cc: &[Complex<Self>], _ch: &mut [Complex<Self>], _wa: &[Complex<Self>], _sign: i8, _fct: Self) -> bool { false }
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f32::EPSILON;
+
+    #[inline] fn from_f64(x: f64) -> Self { x as f32 }
+    #[inline] fn to_f64(self) -> f64 { self as f64 }
+
+    #[inline] fn abs(self) -> Self { libm::fabsf(self) }
+    #[inline] fn sqrt(self) -> Self { libm::sqrtf(self) }
+    #[inline] fn exp(self) -> Self { libm::expf(self) }
+    #[inline] fn ln(self) -> Self { libm::logf(self) }
+    #[inline] fn powf(self, rhs: Self) -> Self { libm::powf(self, rhs) }
+    #[inline] fn sin(self) -> Self { libm::sinf(self) }
+    #[inline] fn cos(self) -> Self { libm::cosf(self) }
+    #[inline] fn atan2(self, rhs: Self) -> Self { libm::atan2f(self, rhs) }
+
+    #[inline] fn is_nan(self) -> bool { f32::is_nan(self) }
+    #[inline] fn is_infinite(self) -> bool { f32::is_infinite(self) }
+    #[inline] fn is_finite(self) -> bool { f32::is_finite(self) }
+    #[inline] fn is_normal(self) -> bool { f32::is_normal(self) }
+    #[inline] fn is_subnormal(self) -> bool { f32::is_subnormal(self) }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f64::EPSILON;
+
+    #[inline] fn from_f64(x: f64) -> Self { x }
+    #[inline] fn to_f64(self) -> f64 { self }
+
+    #[inline] fn abs(self) -> Self { libm::fabs(self) }
+    #[inline] fn sqrt(self) -> Self { libm::sqrt(self) }
+    #[inline] fn exp(self) -> Self { libm::exp(self) }
+    #[inline] fn ln(self) -> Self { libm::log(self) }
+    #[inline] fn powf(self, rhs: Self) -> Self { libm::pow(self, rhs) }
+    #[inline] fn sin(self) -> Self { libm::sin(self) }
+    #[inline] fn cos(self) -> Self { libm::cos(self) }
+    #[inline] fn atan2(self, rhs: Self) -> Self { libm::atan2(self, rhs) }
+
+    #[inline] fn is_nan(self) -> bool { f64::is_nan(self) }
+    #[inline] fn is_infinite(self) -> bool { f64::is_infinite(self) }
+    #[inline] fn is_finite(self) -> bool { f64::is_finite(self) }
+    #[inline] fn is_normal(self) -> bool { f64::is_normal(self) }
+    #[inline] fn is_subnormal(self) -> bool { f64::is_subnormal(self) }
+
+    #[cfg(feature = "simd")]
+    fn simd_pass(ip: usize, ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8, fct: Self) -> bool {
+        crate::simd::pass(ip, ido, l1, cc, ch, wa, sign, fct)
+    }
+}
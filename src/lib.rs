@@ -1,14 +1,45 @@
 #![no_std]
+#![cfg_attr(not(feature = "simd"), forbid(unsafe_code))]
+#![cfg_attr(feature = "simd", deny(unsafe_code))]
 
 extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
 
-mod algorithms; mod math;
-pub use algorithms::CfftPlan;
+mod algorithms; mod conv; mod error; mod fir; mod float; mod math; mod norm; mod planner; mod stft; mod window; mod wisdom;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "simd")]
+#[allow(unsafe_code)]
+mod simd;
+#[cfg(test)]
+mod testing;
+pub use algorithms::{
+    zoom_fft, Algorithm, CfftPlan, CfftPlanNd, CztPlan, DctPlan, DctType, DstPlan, MdctPlan, MdctWindow, PlanBuilder,
+    PlanInfo, RfftPlan
+};
+pub use conv::{
+    autocorrelate, autocorrelate_complex, convolve, convolve_complex, correlate, correlate_complex,
+    ConvMode, Convolver
+};
+pub use error::FftError;
+pub use fir::{FirFilter, FirMethod};
+pub use float::Float;
+pub use norm::Norm;
+#[cfg(feature = "parallel")]
+pub use parallel::{num_threads, set_num_threads};
+#[cfg(target_has_atomic = "ptr")]
+pub use planner::Planner;
+pub use planner::{PlannerStats, RcPlanner};
+#[cfg(feature = "simd")]
+pub use simd::{set_simd_level, simd_level, SimdLevel};
+pub use stft::{check_cola, Istft, PadMode, Stft};
+pub use window::{coherent_gain, enbw, Window};
 
-pub type Result = core::result::Result<(), ()>;
+pub type Result<T = ()> = core::result::Result<T, FftError>;
 
 #[cfg(feature = "num-complex")]
-pub use num_complex::Complex64 as Complex;
+pub type Complex<T = f64> = num_complex::Complex<T>;
 
 #[cfg(not(feature = "num-complex"))]
 mod complex;
@@ -20,7 +51,7 @@ trait ComplexExt {
     fn rotm90(&self) -> Self;
 }
 
-impl ComplexExt for Complex {
+impl<T: Float> ComplexExt for Complex<T> {
     #[inline]
     fn rot90(&self) -> Self {
         Complex::new(-self.im, self.re)
@@ -1,6 +1,7 @@
-use crate::{Complex, ComplexExt};
+use crate::{Complex, ComplexExt, Float};
 use core::f64::consts::PI;
 
+#[allow(clippy::excessive_precision)]
 pub fn my_sincosm1pi(a: f64) -> Complex {
     let mut s = a * a;
     let mut r = -1.0369917389758117e-4;
@@ -21,6 +22,7 @@ pub fn my_sincosm1pi(a: f64) -> Complex {
     return Complex::new(c, s);
 }
 
+#[allow(clippy::needless_range_loop)]
 fn calc_first_octant(den: usize, res: &mut [Complex]) {
     let n = (den + 4) >> 3;
     if n == 0 { return; }
@@ -97,6 +99,7 @@ fn calc_first_half(n: usize, res: &mut [Complex]) {
     }
 }
 
+#[allow(clippy::approx_constant, clippy::excessive_precision)]
 fn fill_first_quadrant(n: usize, res: &mut [Complex]) {
     let hsqt2 = 0.707106781186547524400844362104849_f64;
     let quart = n >> 2;
@@ -152,11 +155,14 @@ fn sincos_2pibyn_half(n: usize, res: &mut [Complex]) {
     else { calc_first_half(n, res); }
 }
 
-pub fn sincos_2pibyn(n: usize, res: &mut [Complex]) {
-    sincos_2pibyn_half(n, res);
-    fill_second_half(n, res);
+pub fn sincos_2pibyn<T: Float>(n: usize, res: &mut [Complex<T>]) {
+    let mut tmp = alloc::vec![Complex::new(0.0, 0.0); n];
+    sincos_2pibyn_half(n, &mut tmp);
+    fill_second_half(n, &mut tmp);
+    for (r, t) in res.iter_mut().zip(tmp) { *r = Complex::new(T::from_f64(t.re), T::from_f64(t.im)); }
 }
 
+#[allow(clippy::manual_is_multiple_of)]
 pub fn largest_prime_factor(mut n: usize) -> usize {
     let mut max_prime = 1;
     while n & 1 == 0 {
@@ -175,8 +181,10 @@ pub fn largest_prime_factor(mut n: usize) -> usize {
     return max_prime;
 }
 
+#[allow(clippy::manual_is_multiple_of)]
 pub fn cost_guess(mut n: usize) -> f64 {
     const LFP: f64 = 1.1;
+    if n == 0 { return 0.0; }
     let ni = n;
     let mut result = 0.0;
     while n & 1 == 0 {
@@ -211,4 +219,35 @@ pub fn good_size(n: usize) -> usize {
     f2 *= 2; }
 
     return bestfac;
+}
+
+// modified bessel function of the first kind, order zero. the power series only has
+// positive terms so it stays accurate for the arguments kaiser style windows throw at it
+pub fn bessel_i0(x: f64) -> f64 {
+    let q = 0.25 * x * x;
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+    while term > sum * 1e-17 {
+        term *= q / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    return sum;
+}
+
+// e^-|x| i0(x), which stays finite where i0 itself overflows (past about 713). the series covers
+// moderate x, the asymptotic expansion the rest; its terms only start growing again around k = 2x
+pub fn bessel_i0_scaled(x: f64) -> f64 {
+    let x = x.abs();
+    if x < 500.0 { return bessel_i0(x) * libm::exp(-x); }
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+    while term > sum * 1e-17 {
+        term *= (2.0 * k - 1.0) * (2.0 * k - 1.0) / (8.0 * x * k);
+        sum += term;
+        k += 1.0;
+    }
+    return sum / libm::sqrt(2.0 * core::f64::consts::PI * x);
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use crate::Float;
+
+// same meaning as numpy.fft / scipy.fft `norm=`, names the direction that gets scaled
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Norm {
+    #[default]
+    Backward, // forward as is, backward by 1/n
+    Ortho, // both ways by 1/sqrt(n), unitary
+    Forward, // forward by 1/n, backward as is
+    Custom(f64) // whatever you pass, both ways
+}
+
+impl Norm {
+    pub fn forward_factor(self, n: usize) -> f64 { self.factor(n, -1) }
+    pub fn backward_factor(self, n: usize) -> f64 { self.factor(n, 1) }
+
+    pub(crate) fn fct<T: Float>(self, n: usize, sign: i8) -> T { T::from_f64(self.factor(n, sign)) }
+
+    fn factor(self, n: usize, sign: i8) -> f64 {
+        let n = if n == 0 { 1.0 } else { n as f64 };
+        match (self, sign < 0) {
+            (Self::Backward, true) | (Self::Forward, false) => 1.0,
+            (Self::Backward, false) | (Self::Forward, true) => 1.0 / n,
+            (Self::Ortho, _) => 1.0 / libm::sqrt(n),
+            (Self::Custom(fct), _) => fct
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::{complex_signal, dft, err}, Algorithm, Complex, PlanBuilder};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn factors() {
+        assert_eq!((Norm::Backward.forward_factor(8), Norm::Backward.backward_factor(8)), (1.0, 0.125));
+        assert_eq!((Norm::Forward.forward_factor(8), Norm::Forward.backward_factor(8)), (0.125, 1.0));
+        assert_eq!((Norm::Ortho.forward_factor(16), Norm::Ortho.backward_factor(16)), (0.25, 0.25));
+        assert_eq!((Norm::Custom(3.0).forward_factor(8), Norm::Custom(3.0).backward_factor(8)), (3.0, 3.0));
+        assert_eq!(Norm::Backward.backward_factor(0), 1.0);
+    }
+
+    // every algorithm scales the same way, down to a single element
+    #[test]
+    fn every_algorithm_scales() {
+        let cases = [(Algorithm::Ct, 1), (Algorithm::Ct, 12), (Algorithm::Bs, 1), (Algorithm::Bs, 12), (Algorithm::Rd, 13), (Algorithm::Gt, 12)];
+        for (algorithm, n) in cases {
+            let plan = PlanBuilder::new(n).algorithm(algorithm).build::<f64>().unwrap();
+            let x = complex_signal(n, 4);
+            for norm in [Norm::Backward, Norm::Ortho, Norm::Forward, Norm::Custom(0.3)] {
+                let mut y = x.clone();
+                plan.forward(&mut y, norm).unwrap();
+                let want: Vec<Complex> = dft(&x, -1).iter().map(|&v| v * norm.forward_factor(n)).collect();
+                assert!(err(&y, &want) < 1e-13, "{:?} {} {:?}", algorithm, n, norm);
+                plan.backward(&mut y, norm).unwrap();
+                let want: Vec<Complex> = x.iter().map(|&v| v * (n as f64 * norm.forward_factor(n) * norm.backward_factor(n))).collect();
+                assert!(err(&y, &want) < 1e-13, "{:?} {} {:?}", algorithm, n, norm);
+            }
+        }
+    }
+}
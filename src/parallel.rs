@@ -0,0 +1,115 @@
+// worker threads for the parallel feature. spawned per call with thread::scope, no pool, so
+// only work big enough to pay for the spawns gets split. every piece of work is computed exactly
+// the way the single threaded path computes it, only somewhere else, so the results don't
+// depend on the thread count
+
+use crate::Result;
+use alloc::vec::Vec;
+use core::{cell::Cell, ops::Range, sync::atomic::{AtomicUsize, Ordering}};
+use std::{panic, thread};
+
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+    static IN_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+// process wide, 0 (the default) means whatever the os says is available
+pub fn set_num_threads(n: usize) { THREADS.store(n, Ordering::Relaxed); }
+
+pub fn num_threads() -> usize {
+    let n = THREADS.load(Ordering::Relaxed);
+    if n != 0 { return n; }
+    return thread::available_parallelism().map_or(1, |n| n.get());
+}
+
+// how many threads `work` should go to with at least `grain` each. work that's already on a
+// worker stays there, nested transforms don't spawn again
+pub fn split(work: usize, grain: usize) -> usize {
+    if IN_WORKER.with(|w| w.get()) { return 1; }
+    return num_threads().min(work / grain.max(1)).max(1);
+}
+
+// part t of 0..len cut into `parts` near equal ranges
+pub fn chunk(len: usize, parts: usize, t: usize) -> Range<usize> { len * t / parts..len * (t + 1) / parts }
+
+// runs every job, the last one on the calling thread, and hands back the first error in job order
+pub fn run<F: FnOnce() -> Result + Send>(mut jobs: Vec<F>) -> Result {
+    let last = jobs.pop();
+    return thread::scope(|s| {
+        let handles: Vec<_> = jobs.into_iter().map(|job| s.spawn(|| in_worker(job))).collect();
+        let last = last.map_or(Ok(()), in_worker);
+        let mut result = Ok(());
+        for h in handles {
+            let r = h.join().unwrap_or_else(|e| panic::resume_unwind(e));
+            if result.is_ok() { result = r; }
+        }
+        return result.and(last);
+    });
+}
+
+fn in_worker<F: FnOnce() -> Result>(job: F) -> Result {
+    let _outer = Restore(IN_WORKER.with(|w| w.replace(true)));
+    return job();
+}
+
+// puts the flag back on the way out, a panicking job included
+struct Restore(bool);
+
+impl Drop for Restore {
+    fn drop(&mut self) { IN_WORKER.with(|w| w.set(self.0)); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::complex_signal, CfftPlan, Complex, Norm};
+
+    #[test]
+    fn chunks_cover_everything() {
+        for (len, parts) in [(10, 3), (7, 7), (100, 1), (5, 8)] {
+            let ranges: Vec<_> = (0..parts).map(|t| chunk(len, parts, t)).collect();
+            assert_eq!(ranges[0].start, 0);
+            assert_eq!(ranges[parts - 1].end, len);
+            assert!(ranges.windows(2).all(|r| r[0].end == r[1].start));
+        }
+    }
+
+    #[test]
+    fn panicking_job_leaves_no_flag_behind() {
+        let caught = panic::catch_unwind(|| in_worker(|| -> Result { std::panic!("job") }));
+        assert!(caught.is_err());
+        assert!(!IN_WORKER.with(|w| w.get()));
+    }
+
+    #[test]
+    fn first_error_wins() {
+        let jobs: Vec<alloc::boxed::Box<dyn FnOnce() -> Result + Send>> = alloc::vec![
+            alloc::boxed::Box::new(|| Ok(())), alloc::boxed::Box::new(|| Err(crate::FftError::UnsupportedSize(1))), alloc::boxed::Box::new(|| Err(crate::FftError::UnsupportedSize(2)))
+        ];
+        assert_eq!(run(jobs), Err(crate::FftError::UnsupportedSize(1)));
+    }
+
+    // big passes and batches split, and every thread count gives the same bits
+    #[test]
+    fn thread_count_changes_nothing() {
+        let outer = THREADS.load(Ordering::Relaxed);
+        let batch = complex_signal(256 * 512, 1);
+        let inputs = [complex_signal(1 << 17, 2), complex_signal(3 * 5 * 7 * 11 * 64, 3)];
+        let run = |threads| {
+            set_num_threads(threads);
+            let mut out = inputs.to_vec();
+            for y in &mut out { CfftPlan::new(y.len()).forward(y, Norm::Ortho).unwrap(); }
+            let mut y = batch.clone();
+            CfftPlan::new(256).forward_batch(&mut y, 512, 1, 256, Norm::Backward).unwrap();
+            out.push(y);
+            return out;
+        };
+        let bits = |v: &[Complex]| v.iter().map(|c| (c.re.to_bits(), c.im.to_bits())).collect::<Vec<_>>();
+        let one = run(1);
+        for threads in [2, 3, 4] {
+            assert!(run(threads).iter().zip(&one).all(|(a, b)| bits(a) == bits(b)), "{} threads", threads);
+        }
+        set_num_threads(outer);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    algorithms::{cooleytukey::CooleyTukey, Algorithm, CfftPlan, RfftPlan, Shared},
+    wisdom::{self, Tuned}, Float, Result
+};
+
+use alloc::{collections::BTreeMap, rc::Rc, string::String};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlannerStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub shared: usize, // cooley-tukey plans one plan got to reuse from another
+    pub evictions: usize,
+    pub cached: usize // complex and real plans held right now
+}
+
+// plans by length, built once and handed out behind $ptr. the cooley-tukey plans underneath go
+// in a table of their own, so e.g. a bluestein plan and a plain one of its inner length share
+// twiddles. a plan does both directions, so forward and backward share an entry too.
+// a measuring planner times the candidates for every new complex length instead of trusting
+// cost_guess, and keeps the winners as wisdom. imported wisdom is used by any planner, for plans
+// made after the import
+macro_rules! planner {
+    ($name:ident, $ptr:ident) => {
+        pub struct $name<T = f64> {
+            complex: BTreeMap<usize, $ptr<CfftPlan<T>>>,
+            real: BTreeMap<usize, $ptr<RfftPlan<T>>>,
+            inner: BTreeMap<usize, Shared<CooleyTukey<T>>>,
+            wisdom: BTreeMap<usize, Tuned>,
+            #[cfg(feature = "std")]
+            measure: bool,
+            stats: PlannerStats
+        }
+
+        impl<T: Float> $name<T> {
+            pub fn new() -> Self {
+                return $name {
+                    complex: BTreeMap::new(), real: BTreeMap::new(), inner: BTreeMap::new(),
+                    wisdom: BTreeMap::new(), stats: PlannerStats::default(),
+                    #[cfg(feature = "std")]
+                    measure: false
+                };
+            }
+
+            #[cfg(feature = "std")]
+            pub fn measuring() -> Self { $name { measure: true, ..Self::new() } }
+
+            pub fn plan(&mut self, length: usize) -> $ptr<CfftPlan<T>> {
+                if let Some(plan) = self.complex.get(&length) {
+                    self.stats.hits += 1;
+                    return plan.clone();
+                }
+                self.stats.misses += 1;
+                #[cfg(feature = "std")]
+                if self.measure && !self.wisdom.contains_key(&length) { self.wisdom.insert(length, wisdom::tune::<T>(length)); }
+
+                let (inner, wisdom, stats) = (&mut self.inner, &self.wisdom, &mut self.stats);
+                let mut inner = |n| Self::inner(inner, wisdom, stats, n);
+                let plan = $ptr::new(match wisdom.get(&length) {
+                    Some(tuned) => CfftPlan::with_algorithm(length, tuned.algorithm, &mut inner),
+                    None => CfftPlan::with_inner(length, &mut inner)
+                });
+                self.complex.insert(length, plan.clone());
+                return plan;
+            }
+
+            pub fn plan_forward(&mut self, length: usize) -> $ptr<CfftPlan<T>> { self.plan(length) }
+            pub fn plan_backward(&mut self, length: usize) -> $ptr<CfftPlan<T>> { self.plan(length) }
+
+            pub fn plan_real(&mut self, length: usize) -> $ptr<RfftPlan<T>> {
+                if let Some(plan) = self.real.get(&length) {
+                    self.stats.hits += 1;
+                    return plan.clone();
+                }
+                self.stats.misses += 1;
+                let (inner, wisdom, stats) = (&mut self.inner, &self.wisdom, &mut self.stats);
+                let plan = $ptr::new(RfftPlan::with_inner(length, &mut |n| Self::inner(inner, wisdom, stats, n)));
+                self.real.insert(length, plan.clone());
+                return plan;
+            }
+
+            // radices in the order wisdom says, if it says anything
+            fn inner(inner: &mut BTreeMap<usize, Shared<CooleyTukey<T>>>, wisdom: &BTreeMap<usize, Tuned>, stats: &mut PlannerStats, n: usize) -> Shared<CooleyTukey<T>> {
+                if let Some(plan) = inner.get(&n) {
+                    stats.shared += 1;
+                    return plan.clone();
+                }
+                let plan = Shared::new(match wisdom.get(&n) {
+                    Some(tuned) if tuned.algorithm == Algorithm::Ct => CooleyTukey::with_factors(n, &tuned.factors).unwrap_or_else(|_| CooleyTukey::new(n)),
+                    _ => CooleyTukey::new(n)
+                });
+                inner.insert(n, plan.clone());
+                return plan;
+            }
+
+            pub fn export_wisdom(&self) -> String { wisdom::export::<T>(&self.wisdom) }
+
+            // merges over what's already known, returns how many lengths the text had for T
+            pub fn import_wisdom(&mut self, text: &str) -> Result<usize> {
+                let entries = wisdom::import::<T>(text)?;
+                let count = entries.len();
+                self.wisdom.extend(entries);
+                return Ok(count);
+            }
+
+            pub fn stats(&self) -> PlannerStats { PlannerStats { cached: self.complex.len() + self.real.len(), ..self.stats } }
+
+            // forgets the complex and real plans of this length, false if there weren't any.
+            // anything already handed out keeps working
+            pub fn evict(&mut self, length: usize) -> bool {
+                let evicted = self.complex.remove(&length).is_some() as usize + self.real.remove(&length).is_some() as usize;
+                self.stats.evictions += evicted;
+                self.drop_inner();
+                return evicted > 0;
+            }
+
+            // forgets every plan nobody outside the planner holds anymore, returns how many
+            pub fn evict_unused(&mut self) -> usize {
+                let before = self.complex.len() + self.real.len();
+                self.complex.retain(|_, plan| $ptr::strong_count(plan) > 1);
+                self.real.retain(|_, plan| $ptr::strong_count(plan) > 1);
+                let evicted = before - self.complex.len() - self.real.len();
+                self.stats.evictions += evicted;
+                self.drop_inner();
+                return evicted;
+            }
+
+            pub fn clear(&mut self) {
+                self.stats.evictions += self.complex.len() + self.real.len();
+                self.complex.clear();
+                self.real.clear();
+                self.inner.clear();
+            }
+
+            // inner plans only the table still holds
+            fn drop_inner(&mut self) { self.inner.retain(|_, plan| Shared::strong_count(plan) > 1); }
+        }
+
+        impl<T: Float> Default for $name<T> {
+            fn default() -> Self { Self::new() }
+        }
+    };
+}
+
+#[cfg(target_has_atomic = "ptr")]
+planner!(Planner, Arc);
+planner!(RcPlanner, Rc);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::{complex_signal, dft, err}, Norm};
+
+    fn matches_dft(plan: &CfftPlan) -> bool {
+        let x = complex_signal(plan.len(), plan.len() as u64);
+        let mut y = x.clone();
+        plan.forward(&mut y, Norm::Backward).unwrap();
+        return err(&y, &dft(&x, -1)) < 1e-13;
+    }
+
+    #[test]
+    fn hands_out_the_same_plan() {
+        let mut planner = RcPlanner::<f64>::new();
+        let a = planner.plan_forward(1000);
+        let b = planner.plan_backward(1000);
+        assert!(Rc::ptr_eq(&a, &b));
+        planner.plan_real(1000);
+        planner.plan_real(1000);
+        let stats = planner.stats();
+        assert_eq!((stats.hits, stats.misses, stats.cached), (2, 2, 2));
+    }
+
+    // 709 and 719 are bluestein plans padded to the same 1440
+    #[test]
+    fn bluestein_lengths_share_the_padded_plan() {
+        let mut planner = RcPlanner::<f64>::new();
+        let (a, b) = (planner.plan(709), planner.plan(719));
+        assert!(matches!(*a, CfftPlan::Bs(_)) && matches!(*b, CfftPlan::Bs(_)));
+        assert_eq!(planner.stats().shared, 1);
+        assert!(matches_dft(&a) && matches_dft(&b));
+    }
+
+    // rader on 1009 convolves at 1008, and good-thomas on 2018 = 2 x 1009 has a rader column plan
+    #[test]
+    fn rader_and_good_thomas_share_inner_plans() {
+        let mut planner = RcPlanner::<f64>::new();
+        let rader = planner.plan(1009);
+        assert!(matches!(*rader, CfftPlan::Rd(_)));
+        planner.plan(1008);
+        assert_eq!(planner.stats().shared, 1);
+
+        let gt = planner.plan(2018);
+        assert!(matches!(*gt, CfftPlan::Gt(_)));
+        assert_eq!(planner.stats().shared, 2);
+        assert!(matches_dft(&rader) && matches_dft(&gt));
+    }
+
+    #[test]
+    fn eviction() {
+        let mut planner = RcPlanner::<f64>::new();
+        let kept = planner.plan(64);
+        planner.plan(100);
+        planner.plan_real(100);
+        assert!(planner.evict(100));
+        assert!(!planner.evict(100));
+        assert_eq!(planner.stats().evictions, 2);
+
+        planner.plan(128);
+        assert_eq!(planner.evict_unused(), 1);
+        assert_eq!(planner.stats().cached, 1);
+        assert!(Rc::ptr_eq(&kept, &planner.plan(64)));
+
+        planner.clear();
+        assert_eq!(planner.stats().cached, 0);
+        assert!(!Rc::ptr_eq(&kept, &planner.plan(64)));
+        assert!(matches_dft(&kept));
+    }
+}
@@ -0,0 +1,147 @@
+// the fixed radix passes written once against a vector of $v::W complex lanes and stamped out
+// per instruction set, every fn carrying the same #[target_feature] so the lane methods and
+// intrinsics inline and calls between them need no unsafe. same order of operations as the
+// scalar kernels in cooleytukey.rs, so without fma the results match them bit for bit and
+// with fma they stay within a few ulps of the output's magnitude
+
+// cos and sin of 2 pi r / p for r in 1..=p / 2, same values as the scalar passes
+pub const TW3: [(f64, f64); 1] = [(-0.5, 0.8660254037844386)];
+pub const TW5: [(f64, f64); 2] = [(0.30901699437494745, 0.9510565162951535), (-0.8090169943749475, 0.5877852522924731)];
+pub const TW7: [(f64, f64); 3] = [
+    (0.6234898018587335, 0.7818314824680298),
+    (-0.2225209339563144, 0.9749279121818236),
+    (-0.9009688679024191, 0.4338837391175581)
+];
+pub const TW11: [(f64, f64); 5] = [
+    (0.8412535328311812, 0.5406408174555976),
+    (0.41541501300188644, 0.9096319953545183),
+    (-0.14231483827328514, 0.9898214418809327),
+    (-0.6548607339452851, 0.7557495743542583),
+    (-0.9594929736144974, 0.28173255684142967)
+];
+
+macro_rules! kernels {
+    ($v:ident, $feat:literal) => {
+        #[target_feature(enable = $feat)]
+        #[inline]
+        fn pmc(a: $v, b: $v) -> ($v, $v) { (a.add(b), a.sub(b)) }
+
+        #[target_feature(enable = $feat)]
+        #[inline]
+        fn bfly2(x: [$v; 2], _sign: i8) -> [$v; 2] {
+            let (a, b) = pmc(x[0], x[1]);
+            return [a, b];
+        }
+
+        #[target_feature(enable = $feat)]
+        #[inline]
+        fn bfly4(x: [$v; 4], sign: i8) -> [$v; 4] {
+            let (t2, t1) = pmc(x[0], x[2]);
+            let (t3, t4) = pmc(x[1], x[3]);
+            let t4 = if sign < 0 { t4.rotm90() } else { t4.rot90() };
+            let (y0, y2) = pmc(t2, t3);
+            let (y1, y3) = pmc(t1, t4);
+            return [y0, y1, y2, y3];
+        }
+
+        // any odd prime p with h = p / 2 twiddle pairs. output m mixes the pair sums with cos(2 pi m j / p)
+        // and the pair differences with sin(2 pi m j / p), folded back into the first h
+        #[target_feature(enable = $feat)]
+        #[inline]
+        fn bfly_odd<const P: usize>(x: [$v; P], tw: &[(f64, f64)], sign: i8) -> [$v; P] {
+            let h = P / 2;
+            let mut sum = [x[0]; P]; // pair sums in 1..=h, differences in h + 1..P
+            for j in 1..=h { (sum[j], sum[P - j]) = pmc(x[j], x[P - j]); }
+
+            let mut y = [x[0]; P];
+            let mut acc = x[0];
+            for j in 1..=h { acc = acc.add(sum[j]); }
+            y[0] = acc;
+
+            for m in 1..=h {
+                let mut ca = x[0];
+                let mut cb = x[0];
+                for j in 1..=h {
+                    let r = m * j % P;
+                    let (c, s) = if r <= h { (tw[r - 1].0, tw[r - 1].1) } else { (tw[P - r - 1].0, -tw[P - r - 1].1) };
+                    ca = sum[j].fma(c, ca);
+                    let s = if sign < 0 { -s } else { s };
+                    cb = if j == 1 { sum[P - j].scale(s) } else { sum[P - j].fma(s, cb) };
+                }
+                (y[m], y[P - m]) = pmc(ca, cb.rot90());
+            }
+            return y;
+        }
+
+        // the (k, i) with k * ido + i in start..start + rows[0].len(), written to the rows of ch.
+        // every lane is worked on its own, so where a range starts doesn't change a single bit
+        #[allow(clippy::too_many_arguments)]
+        #[target_feature(enable = $feat)]
+        #[inline]
+        fn pass<const R: usize>(
+            ido: usize, start: usize, cc: &[Complex], rows: &mut [&mut [Complex]], wa: &[Complex], sign: i8, fct: f64,
+            bfly: impl Fn([$v; R]) -> [$v; R]
+        ) {
+            let end = start + rows[0].len();
+            if ido == 1 {
+                let mut k = start;
+                while k < end {
+                    let n = $v::W.min(end - k);
+                    let mut x: [$v; R] = core::array::from_fn(|j| $v::load(n, |l| cc[(k + l) * R + j]));
+                    if fct != 1.0 { x.iter_mut().for_each(|v| *v = v.scale(fct)); }
+                    let y = bfly(x);
+                    for j in 0..R { y[j].store(n, |l, c| rows[j][k + l - start] = c); }
+                    k += $v::W;
+                }
+                return;
+            }
+
+            let mut m = start;
+            while m < end {
+                let (k, mut i) = (m / ido, m % ido);
+                let stop = ido.min(end - k * ido);
+                let at = |i: usize| k * ido + i - start;
+
+                if i == 0 {
+                    let x: [$v; R] = core::array::from_fn(|j| $v::load(1, |_| cc[ido * (j + k * R)]));
+                    let y = bfly(x);
+                    for j in 0..R { y[j].store(1, |_, c| rows[j][at(0)] = c); }
+                    i = 1;
+                }
+
+                while i < stop {
+                    let n = $v::W.min(stop - i);
+                    let x: [$v; R] = core::array::from_fn(|j| $v::load(n, |l| cc[i + l + ido * (j + k * R)]));
+                    let y = bfly(x);
+                    y[0].store(n, |l, c| rows[0][at(i + l)] = c);
+                    for j in 1..R {
+                        let w = $v::load(n, |l| wa[i + l - 1 + (j - 1) * (ido - 1)]);
+                        let z = if sign < 0 { y[j].mul_conj(w) } else { y[j].mul(w) };
+                        z.store(n, |l, c| rows[j][at(i + l)] = c);
+                    }
+                    i += $v::W;
+                }
+                m = k * ido + stop;
+            }
+        }
+
+        // false if ip has no fixed radix kernel
+        #[allow(clippy::too_many_arguments)]
+        #[target_feature(enable = $feat)]
+        #[inline]
+        pub fn run(ip: usize, ido: usize, start: usize, cc: &[Complex], rows: &mut [&mut [Complex]], wa: &[Complex], sign: i8, fct: f64) -> bool {
+            match ip {
+                2 => pass::<2>(ido, start, cc, rows, wa, sign, fct, |x| bfly2(x, sign)),
+                3 => pass::<3>(ido, start, cc, rows, wa, sign, fct, |x| bfly_odd(x, &crate::simd::kernels::TW3, sign)),
+                4 => pass::<4>(ido, start, cc, rows, wa, sign, fct, |x| bfly4(x, sign)),
+                5 => pass::<5>(ido, start, cc, rows, wa, sign, fct, |x| bfly_odd(x, &crate::simd::kernels::TW5, sign)),
+                7 => pass::<7>(ido, start, cc, rows, wa, sign, fct, |x| bfly_odd(x, &crate::simd::kernels::TW7, sign)),
+                11 => pass::<11>(ido, start, cc, rows, wa, sign, fct, |x| bfly_odd(x, &crate::simd::kernels::TW11, sign)),
+                _ => return false
+            }
+            return true;
+        }
+    };
+}
+
+pub(super) use kernels;
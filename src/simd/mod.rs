@@ -0,0 +1,341 @@
+// every line of unsafe in the crate is in this file, and all of it is one of:
+//  - xgetbv for runtime detection on x86_64
+//  - the calls from pass() into the #[target_feature] kernels below. sse2 and neon are part of
+//    the x86_64 and aarch64 baselines, avx and fma only get called once detect() has seen them
+// past that boundary the kernels and lane methods share the same features, so the intrinsics
+// are safe to call and inline. loads and stores go through slice indexing, never raw pointers
+
+mod kernels;
+
+use crate::Complex;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimdLevel {
+    Scalar,
+    Sse2,
+    Avx,
+    Avx2Fma,
+    Neon
+}
+
+impl SimdLevel {
+    fn from_u8(x: u8) -> Self {
+        match x { 1 => Self::Sse2, 2 => Self::Avx, 3 => Self::Avx2Fma, 4 => Self::Neon, _ => Self::Scalar }
+    }
+
+    // whether a cpu that can do `best` can also do self
+    fn within(self, best: SimdLevel) -> bool {
+        match (self, best) {
+            (Self::Scalar, _) => true,
+            (Self::Neon, b) => b == Self::Neon,
+            (_, Self::Neon | Self::Scalar) => false,
+            (a, b) => a as u8 <= b as u8
+        }
+    }
+}
+
+const UNKNOWN: u8 = u8::MAX;
+static BEST: AtomicU8 = AtomicU8::new(UNKNOWN);
+static CURRENT: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+fn best() -> SimdLevel {
+    let best = BEST.load(Ordering::Relaxed);
+    if best != UNKNOWN { return SimdLevel::from_u8(best); }
+    let best = detect();
+    BEST.store(best as u8, Ordering::Relaxed);
+    return best;
+}
+
+// what the fixed radix passes run on right now, the best the cpu has unless told otherwise
+pub fn simd_level() -> SimdLevel {
+    let current = CURRENT.load(Ordering::Relaxed);
+    if current != UNKNOWN { return SimdLevel::from_u8(current); }
+    return best();
+}
+
+// process wide. anything the cpu can't do falls back to the best it can, which is returned
+pub fn set_simd_level(level: SimdLevel) -> SimdLevel {
+    let level = if level.within(best()) { level } else { best() };
+    CURRENT.store(level as u8, Ordering::Relaxed);
+    return level;
+}
+
+#[cfg(target_arch = "x86_64")]
+fn detect() -> SimdLevel {
+    use core::arch::x86_64::{__cpuid, __cpuid_count};
+
+    let (max_leaf, l1) = (__cpuid(0).eax, __cpuid(1));
+    let (fma, osxsave, avx) = (l1.ecx >> 12 & 1 == 1, l1.ecx >> 27 & 1 == 1, l1.ecx >> 28 & 1 == 1);
+    // the os has to save the ymm registers on context switches too, xcr0 bits 1 and 2
+    // SAFETY: xgetbv is only executed when cpuid reported osxsave
+    if !(osxsave && avx) || unsafe { xcr0() } & 6 != 6 { return SimdLevel::Sse2; }
+
+    let avx2 = max_leaf >= 7 && __cpuid_count(7, 0).ebx >> 5 & 1 == 1;
+    return if avx2 && fma { SimdLevel::Avx2Fma } else { SimdLevel::Avx };
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "xsave")]
+fn xcr0() -> u64 { unsafe { core::arch::x86_64::_xgetbv(0) } }
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+fn detect() -> SimdLevel { SimdLevel::Neon }
+
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))))]
+fn detect() -> SimdLevel { SimdLevel::Scalar }
+
+// output rows are split into pieces this big at the least before going to other threads, the
+// same grain as the scalar passes
+#[cfg(feature = "parallel")]
+const PARALLEL_GRAIN: usize = 1 << 15;
+
+// one fixed radix pass on the best kernel available, false if the scalar code has to do it. ch is
+// ip rows of l1 * ido, cut up across threads the same way the scalar passes cut them
+#[allow(clippy::too_many_arguments)]
+pub fn pass(ip: usize, ido: usize, l1: usize, cc: &[Complex], ch: &mut [Complex], wa: &[Complex], sign: i8, fct: f64) -> bool {
+    // read once, so every piece of the pass runs on the same kernel
+    let level = simd_level();
+    if level == SimdLevel::Scalar || !matches!(ip, 2 | 3 | 4 | 5 | 7 | 11) { return false; }
+
+    let m = l1 * ido;
+    let mut rows: [&mut [Complex]; 11] = Default::default();
+    for (row, c) in rows.iter_mut().zip(ch.chunks_exact_mut(m)) { *row = c; }
+
+    #[cfg(feature = "parallel")]
+    {
+        let threads = crate::parallel::split(m * ip, PARALLEL_GRAIN);
+        if threads > 1 {
+            let mut jobs = alloc::vec::Vec::with_capacity(threads);
+            for t in 0..threads {
+                let range = crate::parallel::chunk(m, threads, t);
+                let mut part: [&mut [Complex]; 11] = Default::default();
+                for (p, row) in part.iter_mut().zip(&mut rows[..ip]) {
+                    let (head, tail) = core::mem::take(row).split_at_mut(range.len());
+                    (*p, *row) = (head, tail);
+                }
+                jobs.push(move || { run(level, ip, ido, range.start, cc, &mut part[..ip], wa, sign, fct); Ok(()) });
+            }
+            let _ = crate::parallel::run(jobs); // passes can't fail
+            return true;
+        }
+    }
+    return run(level, ip, ido, 0, cc, &mut rows[..ip], wa, sign, fct);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run(level: SimdLevel, ip: usize, ido: usize, start: usize, cc: &[Complex], rows: &mut [&mut [Complex]], wa: &[Complex], sign: i8, fct: f64) -> bool {
+    match level {
+        // SAFETY: sse2 is part of the x86_64 baseline
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { x86::sse2::run(ip, ido, start, cc, rows, wa, sign, fct) },
+        // SAFETY: simd_level() never reports more than detect() found
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx => unsafe { x86::avx::run(ip, ido, start, cc, rows, wa, sign, fct) },
+        // SAFETY: as above
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2Fma => unsafe { x86::avx2_fma::run(ip, ido, start, cc, rows, wa, sign, fct) },
+        // SAFETY: this arm only exists when neon is enabled for the whole build
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        SimdLevel::Neon => unsafe { arm::run(ip, ido, start, cc, rows, wa, sign, fct) },
+        _ => false
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    pub mod sse2 {
+        use crate::Complex;
+        use core::arch::x86_64::*;
+
+        // one complex per register, [re, im]
+        #[derive(Clone, Copy)]
+        pub struct Sse2(__m128d);
+
+        impl Sse2 {
+            const W: usize = 1;
+
+            #[target_feature(enable = "sse2")]
+            #[inline]
+            fn load(n: usize, f: impl Fn(usize) -> Complex) -> Self {
+                let a = if n > 0 { f(0) } else { Complex::new(0.0, 0.0) };
+                return Sse2(_mm_set_pd(a.im, a.re));
+            }
+
+            #[target_feature(enable = "sse2")]
+            #[inline]
+            fn store(self, n: usize, mut f: impl FnMut(usize, Complex)) {
+                let (re, im) = (_mm_cvtsd_f64(self.0), _mm_cvtsd_f64(_mm_unpackhi_pd(self.0, self.0)));
+                if n > 0 { f(0, Complex::new(re, im)); }
+            }
+
+            #[target_feature(enable = "sse2")] #[inline] fn add(self, rhs: Self) -> Self { Sse2(_mm_add_pd(self.0, rhs.0)) }
+            #[target_feature(enable = "sse2")] #[inline] fn sub(self, rhs: Self) -> Self { Sse2(_mm_sub_pd(self.0, rhs.0)) }
+            #[target_feature(enable = "sse2")] #[inline] fn scale(self, s: f64) -> Self { Sse2(_mm_mul_pd(self.0, _mm_set1_pd(s))) }
+            #[target_feature(enable = "sse2")] #[inline] fn fma(self, s: f64, acc: Self) -> Self { acc.add(self.scale(s)) }
+            #[target_feature(enable = "sse2")] #[inline] fn rot90(self) -> Self { Sse2(_mm_xor_pd(_mm_shuffle_pd::<1>(self.0, self.0), _mm_set_pd(0.0, -0.0))) }
+            #[target_feature(enable = "sse2")] #[inline] fn rotm90(self) -> Self { Sse2(_mm_xor_pd(_mm_shuffle_pd::<1>(self.0, self.0), _mm_set_pd(-0.0, 0.0))) }
+
+            #[target_feature(enable = "sse2")]
+            #[inline]
+            fn mul(self, w: Self) -> Self {
+                let re = _mm_mul_pd(self.0, _mm_unpacklo_pd(w.0, w.0));
+                let im = _mm_mul_pd(_mm_shuffle_pd::<1>(self.0, self.0), _mm_unpackhi_pd(w.0, w.0));
+                return Sse2(_mm_add_pd(re, _mm_xor_pd(im, _mm_set_pd(0.0, -0.0))));
+            }
+
+            #[target_feature(enable = "sse2")]
+            #[inline]
+            fn mul_conj(self, w: Self) -> Self {
+                let re = _mm_mul_pd(self.0, _mm_unpacklo_pd(w.0, w.0));
+                let im = _mm_mul_pd(_mm_shuffle_pd::<1>(self.0, self.0), _mm_unpackhi_pd(w.0, w.0));
+                return Sse2(_mm_add_pd(re, _mm_xor_pd(im, _mm_set_pd(-0.0, 0.0))));
+            }
+        }
+
+        crate::simd::kernels::kernels!(Sse2, "sse2");
+    }
+
+    // two complexes per register, [re0, im0, re1, im1]. the only difference between the two is
+    // how multiply-adds are done
+    macro_rules! avx_lanes {
+        ($m:ident, $t:ident, $feat:literal, $fma:expr, $cmul:expr) => {
+            pub mod $m {
+                use crate::Complex;
+                use core::arch::x86_64::*;
+
+                #[derive(Clone, Copy)]
+                pub struct $t(__m256d);
+
+                impl $t {
+                    const W: usize = 2;
+
+                    #[target_feature(enable = $feat)]
+                    #[inline]
+                    fn load(n: usize, f: impl Fn(usize) -> Complex) -> Self {
+                        let zero = Complex::new(0.0, 0.0);
+                        let (a, b) = (if n > 0 { f(0) } else { zero }, if n > 1 { f(1) } else { zero });
+                        return $t(_mm256_set_pd(b.im, b.re, a.im, a.re));
+                    }
+
+                    #[target_feature(enable = $feat)]
+                    #[inline]
+                    fn store(self, n: usize, mut f: impl FnMut(usize, Complex)) {
+                        let (lo, hi) = (_mm256_castpd256_pd128(self.0), _mm256_extractf128_pd::<1>(self.0));
+                        if n > 0 { f(0, Complex::new(_mm_cvtsd_f64(lo), _mm_cvtsd_f64(_mm_unpackhi_pd(lo, lo)))); }
+                        if n > 1 { f(1, Complex::new(_mm_cvtsd_f64(hi), _mm_cvtsd_f64(_mm_unpackhi_pd(hi, hi)))); }
+                    }
+
+                    #[target_feature(enable = $feat)] #[inline] fn add(self, rhs: Self) -> Self { $t(_mm256_add_pd(self.0, rhs.0)) }
+                    #[target_feature(enable = $feat)] #[inline] fn sub(self, rhs: Self) -> Self { $t(_mm256_sub_pd(self.0, rhs.0)) }
+                    #[target_feature(enable = $feat)] #[inline] fn scale(self, s: f64) -> Self { $t(_mm256_mul_pd(self.0, _mm256_set1_pd(s))) }
+                    #[target_feature(enable = $feat)] #[inline] fn fma(self, s: f64, acc: Self) -> Self { $t($fma(self.0, _mm256_set1_pd(s), acc.0)) }
+
+                    #[target_feature(enable = $feat)]
+                    #[inline]
+                    fn rot90(self) -> Self { $t(_mm256_xor_pd(_mm256_permute_pd::<0b0101>(self.0), _mm256_set_pd(0.0, -0.0, 0.0, -0.0))) }
+
+                    #[target_feature(enable = $feat)]
+                    #[inline]
+                    fn rotm90(self) -> Self { $t(_mm256_xor_pd(_mm256_permute_pd::<0b0101>(self.0), _mm256_set_pd(-0.0, 0.0, -0.0, 0.0))) }
+
+                    // [a.re w.re - a.im w.im, a.im w.re + a.re w.im] per lane
+                    #[target_feature(enable = $feat)]
+                    #[inline]
+                    fn mul(self, w: Self) -> Self {
+                        let im = _mm256_mul_pd(_mm256_permute_pd::<0b0101>(self.0), _mm256_permute_pd::<0b1111>(w.0));
+                        return $t($cmul(self.0, _mm256_movedup_pd(w.0), im));
+                    }
+
+                    #[target_feature(enable = $feat)]
+                    #[inline]
+                    fn mul_conj(self, w: Self) -> Self { self.mul($t(_mm256_xor_pd(w.0, _mm256_set_pd(-0.0, 0.0, -0.0, 0.0)))) }
+                }
+
+                crate::simd::kernels::kernels!($t, $feat);
+            }
+        };
+    }
+
+    avx_lanes!(avx, Avx, "avx", |a, b, c| _mm256_add_pd(c, _mm256_mul_pd(a, b)), |a, b, im| _mm256_addsub_pd(_mm256_mul_pd(a, b), im));
+    avx_lanes!(avx2_fma, Avx2Fma, "avx,avx2,fma", _mm256_fmadd_pd, _mm256_fmaddsub_pd);
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod arm {
+    use crate::Complex;
+    use core::arch::aarch64::*;
+
+    // one complex per register, [re, im]
+    #[derive(Clone, Copy)]
+    pub struct Neon(float64x2_t);
+
+    impl Neon {
+        const W: usize = 1;
+
+        #[target_feature(enable = "neon")]
+        #[inline]
+        fn load(n: usize, f: impl Fn(usize) -> Complex) -> Self {
+            let a = if n > 0 { f(0) } else { Complex::new(0.0, 0.0) };
+            return Neon(vsetq_lane_f64::<1>(a.im, vdupq_n_f64(a.re)));
+        }
+
+        #[target_feature(enable = "neon")]
+        #[inline]
+        fn store(self, n: usize, mut f: impl FnMut(usize, Complex)) {
+            if n > 0 { f(0, Complex::new(vgetq_lane_f64::<0>(self.0), vgetq_lane_f64::<1>(self.0))); }
+        }
+
+        #[target_feature(enable = "neon")] #[inline] fn add(self, rhs: Self) -> Self { Neon(vaddq_f64(self.0, rhs.0)) }
+        #[target_feature(enable = "neon")] #[inline] fn sub(self, rhs: Self) -> Self { Neon(vsubq_f64(self.0, rhs.0)) }
+        #[target_feature(enable = "neon")] #[inline] fn scale(self, s: f64) -> Self { Neon(vmulq_n_f64(self.0, s)) }
+        #[target_feature(enable = "neon")] #[inline] fn fma(self, s: f64, acc: Self) -> Self { Neon(vfmaq_n_f64(acc.0, self.0, s)) }
+
+        // [-im, re] and [im, -re]
+        #[target_feature(enable = "neon")] #[inline] fn rot90(self) -> Self { Neon(vmulq_f64(vextq_f64::<1>(self.0, self.0), vsetq_lane_f64::<0>(-1.0, vdupq_n_f64(1.0)))) }
+        #[target_feature(enable = "neon")] #[inline] fn rotm90(self) -> Self { Neon(vmulq_f64(vextq_f64::<1>(self.0, self.0), vsetq_lane_f64::<1>(-1.0, vdupq_n_f64(1.0)))) }
+
+        #[target_feature(enable = "neon")]
+        #[inline]
+        fn mul(self, w: Self) -> Self { Neon(vfmaq_f64(vmulq_f64(self.0, vdupq_laneq_f64::<0>(w.0)), self.rot90().0, vdupq_laneq_f64::<1>(w.0))) }
+
+        #[target_feature(enable = "neon")]
+        #[inline]
+        fn mul_conj(self, w: Self) -> Self { Neon(vfmaq_f64(vmulq_f64(self.0, vdupq_laneq_f64::<0>(w.0)), self.rotm90().0, vdupq_laneq_f64::<1>(w.0))) }
+    }
+
+    crate::simd::kernels::kernels!(Neon, "neon");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::{complex_signal, err}, CfftPlan, Norm};
+
+    #[test]
+    fn levels_match_scalar() {
+        let outer = simd_level();
+        // every radix with a kernel, alone with ido > 1 and mixed, and a pair big enough to be split
+        for n in [64, 243, 625, 2401, 1331, 9240, 2 * 3 * 4 * 5 * 7 * 11 * 11, 1 << 17] {
+            let x = complex_signal(n, n as u64);
+            let run = |level| {
+                set_simd_level(level);
+                let plan = CfftPlan::new(n);
+                let (mut f, mut b) = (x.clone(), x.clone());
+                plan.forward(&mut f, Norm::Backward).unwrap();
+                plan.backward(&mut b, Norm::Forward).unwrap();
+                return [f, b];
+            };
+            let scalar = run(SimdLevel::Scalar);
+            for level in [SimdLevel::Sse2, SimdLevel::Avx, SimdLevel::Avx2Fma, SimdLevel::Neon] {
+                if !level.within(best()) { continue; }
+                // the same adds and multiplies in the same order, only fused ones round differently
+                let ulps = if matches!(level, SimdLevel::Avx2Fma | SimdLevel::Neon) { 4.0 } else { 0.0 };
+                for (out, scalar) in run(level).iter().zip(&scalar) {
+                    assert!(err(out, scalar) <= ulps * f64::EPSILON, "{n} {level:?}");
+                }
+            }
+        }
+        set_simd_level(outer);
+    }
+}
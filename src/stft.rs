@@ -0,0 +1,241 @@
+use crate::{Complex, FftError, Float, Norm, Result, RfftPlan};
+
+use alloc::vec::Vec;
+
+// how the signal gets extended when frames are centered on the samples
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PadMode {
+    #[default]
+    Zeros,
+    Reflect // mirror without repeating the edge sample, like numpy's "reflect"
+}
+
+// what both directions share: the window sits in the middle of an nfft long frame
+struct Framing<T> {
+    window: Vec<T>,
+    hop: usize,
+    plan: RfftPlan<T>,
+    center: bool
+}
+
+impl<T: Float> Framing<T> {
+    fn new(window: &[T], hop: usize, nfft: usize) -> Result<Self> {
+        if window.is_empty() || hop == 0 { return Err(FftError::UnsupportedSize(0)); }
+        if nfft < window.len() { return Err(FftError::InvalidParameter("nfft shorter than the window")); }
+
+        let mut padded = alloc::vec![T::ZERO; nfft];
+        let left = (nfft - window.len()) / 2;
+        padded[left..left + window.len()].copy_from_slice(window);
+        return Ok(Framing { window: padded, hop, plan: RfftPlan::new(nfft), center: false });
+    }
+
+    fn nfft(&self) -> usize { self.window.len() }
+    fn offset(&self) -> usize { if self.center { self.nfft() / 2 } else { 0 } }
+
+    fn frames(&self, len: usize) -> usize {
+        let padded = len + 2 * self.offset();
+        if padded < self.nfft() { 0 } else { 1 + (padded - self.nfft()) / self.hop }
+    }
+}
+
+pub struct Stft<T = f64> {
+    framing: Framing<T>,
+    pad: PadMode
+}
+
+impl<T: Float> Stft<T> {
+    pub fn new(window: &[T], hop: usize, nfft: usize) -> Result<Self> {
+        return Ok(Stft { framing: Framing::new(window, hop, nfft)?, pad: PadMode::Zeros });
+    }
+
+    // frame t is centered on sample t * hop instead of starting there
+    pub fn centered(mut self, pad: PadMode) -> Self {
+        (self.framing.center, self.pad) = (true, pad);
+        return self;
+    }
+
+    // row-major, frames() rows of bins() spectra each
+    pub fn forward(&self, signal: &[T], norm: Norm) -> Result<Vec<Complex<T>>> {
+        // nothing to mirror
+        if signal.is_empty() && self.pad == PadMode::Reflect { return Err(FftError::UnsupportedSize(0)); }
+        let (nfft, bins) = (self.framing.nfft(), self.bins());
+        let frames = self.frames(signal.len());
+        let offset = self.framing.offset() as isize;
+
+        let mut out = alloc::vec![Complex::new(T::ZERO, T::ZERO); frames * bins];
+        let mut buf = alloc::vec![T::ZERO; nfft];
+        for (t, spec) in out.chunks_exact_mut(bins).enumerate() {
+            let start = (t * self.framing.hop) as isize - offset;
+            for (n, (b, &w)) in buf.iter_mut().zip(&self.framing.window).enumerate() { *b = self.sample(signal, start + n as isize) * w; }
+            self.framing.plan.forward(&buf, spec, norm)?;
+        }
+        return Ok(out);
+    }
+
+    fn sample(&self, signal: &[T], idx: isize) -> T {
+        let len = signal.len() as isize;
+        if idx >= 0 && idx < len { return signal[idx as usize]; }
+        match self.pad {
+            PadMode::Zeros => T::ZERO,
+            PadMode::Reflect if len == 1 => signal[0],
+            PadMode::Reflect => {
+                let period = 2 * (len - 1);
+                let m = idx.rem_euclid(period);
+                signal[(if m < len { m } else { period - m }) as usize]
+            }
+        }
+    }
+
+    pub fn frames(&self, len: usize) -> usize { self.framing.frames(len) }
+    pub fn bins(&self) -> usize { self.framing.nfft() / 2 + 1 }
+    pub fn hop(&self) -> usize { self.framing.hop }
+    pub fn nfft(&self) -> usize { self.framing.nfft() }
+}
+
+// weighted overlap-add: every frame is windowed again after the inverse transform and
+// the sum gets divided by the overlapped squared window, so any window/hop pair whose
+// squared window never overlaps to zero inverts exactly (the NOLA condition)
+pub struct Istft<T = f64> {
+    framing: Framing<T>
+}
+
+impl<T: Float> Istft<T> {
+    pub fn new(window: &[T], hop: usize, nfft: usize) -> Result<Self> {
+        let framing = Framing::new(window, hop, nfft)?;
+        let squared: Vec<T> = framing.window.iter().map(|&w| w * w).collect();
+        let sums = overlap_sums(&squared, hop);
+        let peak = sums.iter().fold(T::ZERO, |m, &s| if s > m { s } else { m });
+        if sums.iter().any(|&s| s <= peak * T::from_f64(1e-10)) { return Err(FftError::InvalidParameter("window and hop violate NOLA")); }
+        return Ok(Istft { framing });
+    }
+
+    pub fn centered(mut self) -> Self {
+        self.framing.center = true;
+        return self;
+    }
+
+    // spectra as produced by Stft::forward, length is how many samples to give back
+    pub fn backward(&self, spectra: &[Complex<T>], length: usize, norm: Norm) -> Result<Vec<T>> {
+        let (nfft, bins) = (self.framing.nfft(), self.framing.nfft() / 2 + 1);
+        if !spectra.len().is_multiple_of(bins) { return Err(FftError::LengthMismatch { expected: spectra.len() / bins * bins, actual: spectra.len() }); }
+        let frames = spectra.len() / bins;
+        let offset = self.framing.offset();
+
+        let total = if frames == 0 { 0 } else { (frames - 1) * self.framing.hop + nfft };
+        let mut acc = alloc::vec![T::ZERO; total.max(length + offset)];
+        let mut env = acc.clone();
+        let mut buf = alloc::vec![T::ZERO; nfft];
+
+        for (t, spec) in spectra.chunks_exact(bins).enumerate() {
+            self.framing.plan.backward(spec, &mut buf, norm)?;
+            let start = t * self.framing.hop;
+            for n in 0..nfft {
+                let w = self.framing.window[n];
+                acc[start + n] += buf[n] * w;
+                env[start + n] += w * w;
+            }
+        }
+
+        let floor = T::from_f64(1e-10) * env.iter().fold(T::ZERO, |m, &s| if s > m { s } else { m });
+        let mut out = alloc::vec![T::ZERO; length];
+        for (n, o) in out.iter_mut().enumerate() {
+            let (a, e) = (acc[n + offset], env[n + offset]);
+            if e > floor { *o = a / e; }
+        }
+        return Ok(out);
+    }
+
+    // whether the plain window overlap-adds to a constant at this hop, the classic
+    // constant overlap-add condition (scipy's check_COLA)
+    pub fn is_cola(&self) -> bool { check_cola(&self.framing.window, self.framing.hop) }
+
+    pub fn hop(&self) -> usize { self.framing.hop }
+    pub fn nfft(&self) -> usize { self.framing.nfft() }
+}
+
+fn overlap_sums<T: Float>(window: &[T], hop: usize) -> Vec<T> {
+    let mut sums = alloc::vec![T::ZERO; hop];
+    for (n, &w) in window.iter().enumerate() { sums[n % hop] += w; }
+    return sums;
+}
+
+pub fn check_cola<T: Float>(window: &[T], hop: usize) -> bool {
+    if hop == 0 { return false; }
+    let sums = overlap_sums(window, hop);
+    let mean = sums.iter().fold(T::ZERO, |a, &s| a + s) / T::from_f64(hop as f64);
+    let tol = T::from_f64(1000.0) * T::EPSILON * mean.abs();
+    return mean != T::ZERO && sums.iter().all(|&s| (s - mean).abs() <= tol);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::{dft, err, err_real, real_to_complex, signal}, Window};
+
+    // frame t is the windowed stretch starting at t * hop (less half a frame when centered),
+    // transformed on its own
+    fn frames_by_dft(stft: &Stft<f64>, signal: &[f64], window: &[f64]) -> Vec<Complex> {
+        let (nfft, hop) = (stft.nfft(), stft.hop());
+        let offset = stft.framing.offset() as isize;
+        let mut out = Vec::new();
+        for t in 0..stft.frames(signal.len()) {
+            let start = (t * hop) as isize - offset;
+            let frame: Vec<f64> = (0..nfft).map(|n| stft.sample(signal, start + n as isize) * window[n]).collect();
+            out.extend_from_slice(&dft(&real_to_complex(&frame), -1)[..nfft / 2 + 1]);
+        }
+        return out;
+    }
+
+    #[test]
+    fn frames_match_dft() {
+        let x = signal(300, 1);
+        let w: Vec<f64> = Window::Hann.periodic(64).unwrap();
+        for stft in [Stft::new(&w, 16, 64).unwrap(), Stft::new(&w, 16, 64).unwrap().centered(PadMode::Zeros), Stft::new(&w, 24, 64).unwrap().centered(PadMode::Reflect)] {
+            let got = stft.forward(&x, Norm::Backward).unwrap();
+            assert_eq!(got.len(), stft.frames(x.len()) * stft.bins());
+            assert!(err(&got, &frames_by_dft(&stft, &x, &w)) < 1e-13);
+        }
+    }
+
+    #[test]
+    fn reflect_padding() {
+        let stft = Stft::<f64>::new(&[1.0; 4], 1, 4).unwrap().centered(PadMode::Reflect);
+        let x = [1.0, 2.0, 3.0];
+        let got: Vec<f64> = (-4..7).map(|i| stft.sample(&x, i)).collect();
+        assert_eq!(got, [1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0]);
+        assert_eq!(stft.sample(&[5.0], -3), 5.0);
+        assert_eq!(stft.forward(&[], Norm::Backward), Err(FftError::UnsupportedSize(0)));
+    }
+
+    // a window shorter than nfft sits in the middle, hann at quarter hop is nola
+    #[test]
+    fn round_trip() {
+        let x = signal(1000, 2);
+        let w: Vec<f64> = Window::Hann.periodic(48).unwrap();
+        for (center, norm) in [(false, Norm::Backward), (true, Norm::Ortho), (true, Norm::Forward)] {
+            let (stft, istft) = (Stft::new(&w, 12, 64).unwrap(), Istft::new(&w, 12, 64).unwrap());
+            let (stft, istft) = if center { (stft.centered(PadMode::Zeros), istft.centered()) } else { (stft, istft) };
+            let spectra = stft.forward(&x, norm).unwrap();
+            let back = istft.backward(&spectra, x.len(), norm).unwrap();
+            // uncentered frames only cover what falls under a nonzero weight, from just past the
+            // 8 samples of padding in front of the window to the end of the last one
+            let (skip, end) = if center { (0, x.len()) } else { (9, (stft.frames(x.len()) - 1) * 12 + 56) };
+            assert!(err_real(&back[skip..end], &x[skip..end]) < 1e-12, "center = {}", center);
+        }
+    }
+
+    #[test]
+    fn cola_and_nola() {
+        assert!(check_cola(&Window::Hann.periodic::<f64>(64).unwrap(), 32));
+        assert!(check_cola(&Window::Hann.periodic::<f64>(64).unwrap(), 16));
+        assert!(!check_cola(&Window::Hann.symmetric::<f64>(64).unwrap(), 32));
+        assert!(!check_cola(&Window::Hann.periodic::<f64>(64).unwrap(), 0));
+        // a rectangle hopping past its own length leaves gaps
+        assert_eq!(Istft::<f64>::new(&[1.0; 16], 20, 16).err(), Some(FftError::InvalidParameter("window and hop violate NOLA")));
+        // a frame can't be shorter than its window
+        assert_eq!(Stft::<f64>::new(&[1.0; 16], 4, 8).err(), Some(FftError::InvalidParameter("nfft shorter than the window")));
+        assert_eq!(Istft::<f64>::new(&[1.0; 16], 4, 8).err(), Some(FftError::InvalidParameter("nfft shorter than the window")));
+        assert!(Istft::<f64>::new(&Window::Hann.periodic(64).unwrap(), 32, 64).unwrap().is_cola());
+    }
+}
@@ -0,0 +1,49 @@
+// reference transforms and inputs for the module tests, straight from the definitions in f64
+use crate::Complex;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+// xorshift, values in [-1, 1)
+pub fn signal(n: usize, seed: u64) -> Vec<f64> {
+    let mut s = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+    return (0..n).map(|_| {
+        s ^= s << 13; s ^= s >> 7; s ^= s << 17;
+        (s >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }).collect();
+}
+
+pub fn complex_signal(n: usize, seed: u64) -> Vec<Complex> {
+    let v = signal(2 * n, seed);
+    return v.chunks_exact(2).map(|c| Complex::new(c[0], c[1])).collect();
+}
+
+// e^(sign 2 pi i k / n), k reduced first so large products stay exact
+pub fn root(k: usize, n: usize, sign: i8) -> Complex {
+    let a = 2.0 * PI * (k % n) as f64 / n as f64;
+    return Complex::new(libm::cos(a), sign as f64 * libm::sin(a));
+}
+
+// sum over j of x[j] e^(sign 2 pi i j k / n), unscaled
+pub fn dft(x: &[Complex], sign: i8) -> Vec<Complex> {
+    let n = x.len();
+    return (0..n).map(|k| {
+        x.iter().enumerate().fold(Complex::new(0.0, 0.0), |acc, (j, &v)| acc + v * root(j * k % n, n, sign))
+    }).collect();
+}
+
+fn mag(v: Complex) -> f64 { libm::hypot(v.re, v.im) }
+
+// largest deviation relative to the largest magnitude of the reference
+pub fn err(a: &[Complex], b: &[Complex]) -> f64 {
+    assert_eq!(a.len(), b.len());
+    let scale = b.iter().map(|v| mag(*v)).fold(1e-300, f64::max);
+    return a.iter().zip(b).map(|(x, y)| mag(*x - *y)).fold(0.0, f64::max) / scale;
+}
+
+pub fn err_real(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len());
+    let scale = b.iter().map(|v| v.abs()).fold(1e-300, f64::max);
+    return a.iter().zip(b).map(|(x, y)| (x - y).abs()).fold(0.0, f64::max) / scale;
+}
+
+pub fn real_to_complex(x: &[f64]) -> Vec<Complex> { x.iter().map(|&v| Complex::new(v, 0.0)).collect() }
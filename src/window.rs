@@ -0,0 +1,264 @@
+use crate::{math::bessel_i0_scaled, FftError, Float, Result};
+
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+// the usual suspects. symmetric windows are for filter design (both ends equal), periodic
+// ones for spectral analysis (one sample short of a full period, so they tile at their hop)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Window {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+    BlackmanHarris, // 4 term, -92 dB sidelobes
+    FlatTop, // amplitude accurate to a fraction of a dB, same coefficients as scipy
+    Tukey(f64), // fraction of the window inside the cosine tapers, 0 is rectangular and 1 is hann
+    Gaussian(f64), // standard deviation in samples
+    Kaiser(f64), // beta
+    Dpss(f64) // time-halfbandwidth product NW, peak normalised to 1
+}
+
+impl Window {
+    pub fn symmetric<T: Float>(self, len: usize) -> Result<Vec<T>> {
+        self.check()?;
+        if len <= 1 { return Ok(alloc::vec![T::ONE; len]); }
+        return Ok(self.samples(len).into_iter().map(T::from_f64).collect());
+    }
+
+    // a symmetric window of len + 1 with the last sample dropped
+    pub fn periodic<T: Float>(self, len: usize) -> Result<Vec<T>> {
+        self.check()?;
+        if len <= 1 { return Ok(alloc::vec![T::ONE; len]); }
+        let mut w = self.samples(len + 1);
+        w.pop();
+        return Ok(w.into_iter().map(T::from_f64).collect());
+    }
+
+    fn check(self) -> Result {
+        match self {
+            Self::Gaussian(std) if !(std > 0.0 && std.is_finite()) => Err(FftError::InvalidParameter("gaussian std must be positive")),
+            Self::Tukey(p) | Self::Kaiser(p) | Self::Dpss(p) if !p.is_finite() => Err(FftError::InvalidParameter("window parameter must be finite")),
+            _ => Ok(())
+        }
+    }
+
+    // len >= 2 from here on
+    fn samples(self, len: usize) -> Vec<f64> {
+        let m = (len - 1) as f64;
+        let x = |n: usize| n as f64 / m; // 0 to 1 across the window
+        match self {
+            Self::Rectangular => alloc::vec![1.0; len],
+            Self::Hann => cosine_sum(len, &[0.5, 0.5]),
+            Self::Hamming => cosine_sum(len, &[0.54, 0.46]),
+            Self::Blackman => cosine_sum(len, &[0.42, 0.5, 0.08]),
+            Self::BlackmanHarris => cosine_sum(len, &[0.35875, 0.48829, 0.14128, 0.01168]),
+            Self::FlatTop => cosine_sum(len, &[0.21557895, 0.41663158, 0.277263158, 0.083578947, 0.006947368]),
+            Self::Tukey(alpha) if alpha <= 0.0 => alloc::vec![1.0; len],
+            Self::Tukey(alpha) if alpha >= 1.0 => cosine_sum(len, &[0.5, 0.5]),
+            Self::Tukey(alpha) => (0..len).map(|n| {
+                let x = x(n).min(1.0 - x(n)); // mirror the right taper onto the left one
+                if x < 0.5 * alpha { 0.5 * (1.0 + libm::cos(PI * (2.0 * x / alpha - 1.0))) } else { 1.0 }
+            }).collect(),
+            Self::Gaussian(std) => (0..len).map(|n| {
+                let d = (n as f64 - 0.5 * m) / std;
+                libm::exp(-0.5 * d * d)
+            }).collect(),
+            // i0(a) / i0(beta) from scaled values, so a big beta doesn't turn into inf / inf
+            Self::Kaiser(beta) => (0..len).map(|n| {
+                let r = 2.0 * x(n) - 1.0;
+                let a = beta.abs() * libm::sqrt((1.0 - r * r).max(0.0));
+                bessel_i0_scaled(a) / bessel_i0_scaled(beta) * libm::exp(a - beta.abs())
+            }).collect(),
+            Self::Dpss(nw) => dpss(len, nw)
+        }
+    }
+}
+
+// w[n] = a0 - a1 cos(2 pi n / m) + a2 cos(4 pi n / m) - ...
+fn cosine_sum(len: usize, a: &[f64]) -> Vec<f64> {
+    let m = (len - 1) as f64;
+    return (0..len).map(|n| {
+        let mut w = 0.0;
+        for (k, &ak) in a.iter().enumerate() {
+            let term = ak * libm::cos(2.0 * PI * (k * n) as f64 / m);
+            if k & 1 == 0 { w += term; } else { w -= term; }
+        }
+        w
+    }).collect();
+}
+
+// the first slepian sequence is the eigenvector of the largest eigenvalue of a symmetric
+// tridiagonal matrix (slepian 1978, same one scipy uses). the eigenvalue comes from sturm
+// sequence bisection, the vector from a couple of rounds of inverse iteration
+fn dpss(len: usize, nw: f64) -> Vec<f64> {
+    let cw = libm::cos(2.0 * PI * nw / len as f64);
+    let diag: Vec<f64> = (0..len).map(|n| {
+        let c = 0.5 * (len as f64 - 1.0) - n as f64;
+        c * c * cw
+    }).collect();
+    let off: Vec<f64> = (1..len).map(|n| 0.5 * (n * (len - n)) as f64).collect();
+
+    // how many eigenvalues lie below x
+    let below = |x: f64| {
+        let (mut q, mut count) = (1.0, 0);
+        for i in 0..len {
+            let e2 = if i == 0 { 0.0 } else { off[i - 1] * off[i - 1] };
+            q = diag[i] - x - if q != 0.0 { e2 / q } else { e2 / f64::EPSILON };
+            if q < 0.0 { count += 1; }
+        }
+        count
+    };
+
+    // gershgorin bounds, then bisect for the top eigenvalue
+    let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+    for i in 0..len {
+        let r = if i > 0 { off[i - 1] } else { 0.0 } + if i + 1 < len { off[i] } else { 0.0 };
+        lo = lo.min(diag[i] - r);
+        hi = hi.max(diag[i] + r);
+    }
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi { break; }
+        if below(mid) == len { hi = mid; } else { lo = mid; }
+    }
+
+    // shift a hair past the eigenvalue so the solve stays well defined
+    let shift = hi + (hi - lo).max(hi.abs() * 1e-14);
+    let mut v = alloc::vec![1.0; len];
+    let mut pivot = alloc::vec![0.0; len];
+    for _ in 0..3 {
+        // gaussian elimination on (T - shift I) v' = v, no pivoting needed since the
+        // shifted matrix is negative definite
+        pivot[0] = diag[0] - shift;
+        for i in 1..len {
+            let m = off[i - 1] / pivot[i - 1];
+            pivot[i] = diag[i] - shift - m * off[i - 1];
+            v[i] -= m * v[i - 1];
+        }
+        v[len - 1] /= pivot[len - 1];
+        for i in (0..len - 1).rev() { v[i] = (v[i] - off[i] * v[i + 1]) / pivot[i]; }
+
+        let peak = v.iter().fold(0.0_f64, |m, &x| if x.abs() > m.abs() { x } else { m });
+        v.iter_mut().for_each(|x| *x /= peak);
+    }
+    return v;
+}
+
+// mean of the window, what a windowed sinusoid's peak gets scaled by. zero for an empty window
+pub fn coherent_gain<T: Float>(window: &[T]) -> T {
+    if window.is_empty() { return T::ZERO; }
+    let sum = window.iter().fold(T::ZERO, |a, &w| a + w);
+    return sum / T::from_f64(window.len() as f64);
+}
+
+// equivalent noise bandwidth in bins, n * sum(w^2) / sum(w)^2. 1 for the rectangle, 1.5 for hann,
+// zero for an empty window
+pub fn enbw<T: Float>(window: &[T]) -> T {
+    if window.is_empty() { return T::ZERO; }
+    let (sum, sq) = window.iter().fold((T::ZERO, T::ZERO), |(a, b), &w| (a + w, b + w * w));
+    return T::from_f64(window.len() as f64) * sq / (sum * sum);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &[f64], b: &[f64], tol: f64) -> bool { a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < tol) }
+
+    #[test]
+    fn cosine_windows() {
+        let len = 33;
+        let m = (len - 1) as f64;
+        let hann: Vec<f64> = (0..len).map(|n| 0.5 - 0.5 * libm::cos(2.0 * PI * n as f64 / m)).collect();
+        assert!(close(&Window::Hann.symmetric(len).unwrap(), &hann, 1e-15));
+        let hamming: Vec<f64> = (0..len).map(|n| 0.54 - 0.46 * libm::cos(2.0 * PI * n as f64 / m)).collect();
+        assert!(close(&Window::Hamming.symmetric(len).unwrap(), &hamming, 1e-15));
+        // the flat top peaks at 1 in the middle, blackman-harris nearly vanishes at the ends
+        assert!((Window::FlatTop.symmetric::<f64>(len).unwrap()[len / 2] - 1.0).abs() < 1e-8);
+        assert!(Window::BlackmanHarris.symmetric::<f64>(len).unwrap()[0] < 1e-4);
+    }
+
+    #[test]
+    fn periodic_drops_the_last_sample() {
+        for window in [Window::Hann, Window::Blackman, Window::Kaiser(8.0), Window::Tukey(0.3), Window::Dpss(2.5)] {
+            let sym: Vec<f64> = window.symmetric(65).unwrap();
+            assert!(close(&window.periodic(64).unwrap(), &sym[..64], 1e-12), "{:?}", window);
+        }
+        assert_eq!(Window::Hann.periodic::<f64>(1).unwrap(), [1.0]);
+        assert!(Window::Hann.symmetric::<f64>(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parameter_limits() {
+        let rect = alloc::vec![1.0; 20];
+        assert!(close(&Window::Tukey(0.0).symmetric(20).unwrap(), &rect, 1e-15));
+        assert!(close(&Window::Tukey(1.0).symmetric(20).unwrap(), &Window::Hann.symmetric::<f64>(20).unwrap(), 1e-15));
+        assert!(close(&Window::Kaiser(0.0).symmetric(20).unwrap(), &rect, 1e-15));
+        let g: Vec<f64> = Window::Gaussian(3.0).symmetric(21).unwrap();
+        assert!((g[10] - 1.0).abs() < 1e-15 && (g[13] - libm::exp(-0.5)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        for window in [Window::Gaussian(0.0), Window::Gaussian(-1.0), Window::Gaussian(f64::NAN), Window::Gaussian(f64::INFINITY)] {
+            assert_eq!(window.symmetric::<f64>(16), Err(FftError::InvalidParameter("gaussian std must be positive")));
+            assert!(window.periodic::<f64>(16).is_err());
+        }
+        for window in [Window::Tukey(f64::NAN), Window::Kaiser(f64::INFINITY), Window::Dpss(f64::NAN)] {
+            assert_eq!(window.periodic::<f64>(16), Err(FftError::InvalidParameter("window parameter must be finite")));
+        }
+    }
+
+    // i0 overflows past about 713, the window shouldn't care
+    #[test]
+    fn kaiser_with_big_beta() {
+        use crate::math::{bessel_i0, bessel_i0_scaled};
+        for beta in [0.5, 8.0, 100.0, 600.0] {
+            let w: Vec<f64> = Window::Kaiser(beta).symmetric(51).unwrap();
+            let direct = (0..51).map(|n| {
+                let r = n as f64 / 25.0 - 1.0;
+                bessel_i0(beta * libm::sqrt((1.0 - r * r).max(0.0))) / bessel_i0(beta)
+            });
+            assert!(w.iter().zip(direct).all(|(a, b)| (a - b).abs() <= 1e-13 * b), "{}", beta);
+        }
+        for beta in [800.0, 5000.0, 1e6] {
+            let w: Vec<f64> = Window::Kaiser(beta).symmetric(51).unwrap();
+            assert!(w.iter().all(|x| (0.0..=1.0).contains(x)), "{}", beta);
+            assert_eq!(w[25], 1.0);
+        }
+        // the series and the asymptotic expansion agree where they hand over
+        let (series, asymptotic) = (bessel_i0(500.0) * libm::exp(-500.0), bessel_i0_scaled(500.0));
+        assert!((series - asymptotic).abs() < 1e-13 * asymptotic, "{} {}", series, asymptotic);
+    }
+
+    // the slepian sequence is the top eigenvector of the tridiagonal matrix built in dpss
+    #[test]
+    fn dpss_is_the_eigenvector() {
+        for (len, nw) in [(16, 2.0), (63, 3.5), (128, 4.0)] {
+            let v: Vec<f64> = Window::Dpss(nw).symmetric(len).unwrap();
+            let cw = libm::cos(2.0 * PI * nw / len as f64);
+            let tv: Vec<f64> = (0..len).map(|i| {
+                let c = 0.5 * (len as f64 - 1.0) - i as f64;
+                let mut s = c * c * cw * v[i];
+                if i > 0 { s += 0.5 * (i * (len - i)) as f64 * v[i - 1]; }
+                if i + 1 < len { s += 0.5 * ((i + 1) * (len - i - 1)) as f64 * v[i + 1]; }
+                s
+            }).collect();
+            let lambda = tv[len / 2] / v[len / 2];
+            assert!(v.iter().zip(&tv).all(|(a, b)| (b - lambda * a).abs() < 1e-9 * lambda.abs()), "{} {}", len, nw);
+            assert!((v.iter().cloned().fold(0.0, f64::max) - 1.0).abs() < 1e-15);
+            assert!(close(&v, &v.iter().rev().copied().collect::<Vec<_>>(), 1e-12));
+        }
+    }
+
+    #[test]
+    fn gain_and_bandwidth() {
+        let hann: Vec<f64> = Window::Hann.periodic(256).unwrap();
+        assert!((coherent_gain(&hann) - 0.5).abs() < 1e-15);
+        assert!((enbw(&hann) - 1.5).abs() < 1e-12);
+        assert!((enbw(&[1.0; 10]) - 1.0_f64).abs() < 1e-15);
+        assert_eq!((coherent_gain::<f64>(&[]), enbw::<f64>(&[])), (0.0, 0.0));
+    }
+}
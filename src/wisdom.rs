@@ -0,0 +1,197 @@
+// what a measuring planner found out, and the text it's saved as. one line per length:
+//
+//   palmfft wisdom 1
+//   f64 1000 ct 4 2 5 5 5
+//   f64 4099 rd
+//   f32 2310 gt
+//
+// cooley-tukey entries carry their radices in order, the rest only the algorithm (their
+// nested plans are chosen the usual way, or from wisdom for the same length)
+
+use crate::{
+    algorithms::{check_algorithm, cooleytukey::check_factors, Algorithm},
+    FftError, Result
+};
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::Write;
+
+const HEADER: &str = "palmfft wisdom 1";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tuned {
+    pub algorithm: Algorithm,
+    pub factors: Vec<usize> // cooley-tukey only
+}
+
+pub fn precision<T>() -> &'static str { if core::mem::size_of::<T>() == 4 { "f32" } else { "f64" } }
+
+pub fn export<T>(wisdom: &BTreeMap<usize, Tuned>) -> String {
+    let mut text = String::from(HEADER);
+    for (n, tuned) in wisdom {
+        let name = match tuned.algorithm { Algorithm::Ct => "ct", Algorithm::Bs => "bs", Algorithm::Rd => "rd", Algorithm::Gt => "gt" };
+        let _ = write!(text, "\n{} {} {}", precision::<T>(), n, name);
+        for f in &tuned.factors { let _ = write!(text, " {}", f); }
+    }
+    text.push('\n');
+    return text;
+}
+
+// lines for the other precision are skipped, anything malformed fails the whole import
+pub fn import<T>(text: &str) -> Result<Vec<(usize, Tuned)>> {
+    let bad = FftError::InvalidParameter("malformed wisdom");
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    if lines.next() != Some(HEADER) { return Err(bad); }
+
+    let mut entries = Vec::new();
+    for line in lines {
+        let mut words = line.split_ascii_whitespace();
+        let (Some(prec), Some(n), Some(name)) = (words.next(), words.next(), words.next()) else { return Err(bad); };
+        let n: usize = n.parse().map_err(|_| bad)?;
+        let algorithm = match name { "ct" => Algorithm::Ct, "bs" => Algorithm::Bs, "rd" => Algorithm::Rd, "gt" => Algorithm::Gt, _ => return Err(bad) };
+        let factors = words.map(|w| w.parse().map_err(|_| bad)).collect::<Result<Vec<usize>>>()?;
+
+        check_algorithm(n, algorithm)?;
+        if algorithm == Algorithm::Ct { check_factors(n, &factors)?; }
+        else if !factors.is_empty() { return Err(bad); }
+        if prec == precision::<T>() { entries.push((n, Tuned { algorithm, factors })); }
+    }
+    return Ok(entries);
+}
+
+// everything worth timing for a length: the usual radix order and a few others, then
+// whichever of bluestein, rader and good-thomas apply
+#[cfg(feature = "std")]
+pub fn candidates(n: usize) -> Vec<Tuned> {
+    use crate::{algorithms::{cooleytukey::factorize, goodthomas}, math::largest_prime_factor};
+
+    // nothing to transform, and nothing factorize could take apart
+    if n < 2 { return alloc::vec![Tuned { algorithm: Algorithm::Ct, factors: Vec::new() }]; }
+    let usual: Vec<usize> = factorize::<f64>(n).iter().map(|f| f.fct).collect();
+    let mut ascending = usual.clone();
+    ascending.sort_unstable();
+    let descending: Vec<usize> = ascending.iter().rev().copied().collect();
+    let reversed: Vec<usize> = usual.iter().rev().copied().collect();
+
+    let mut out: Vec<Tuned> = Vec::new();
+    for factors in [usual, ascending, descending, reversed] {
+        if !out.iter().any(|t| t.factors == factors) { out.push(Tuned { algorithm: Algorithm::Ct, factors }); }
+    }
+    if n < 3 { return out; }
+
+    if largest_prime_factor(n) > 11 { out.push(Tuned { algorithm: Algorithm::Bs, factors: Vec::new() }); }
+    if largest_prime_factor(n) == n { out.push(Tuned { algorithm: Algorithm::Rd, factors: Vec::new() }); }
+    if goodthomas::split(n).is_some() { out.push(Tuned { algorithm: Algorithm::Gt, factors: Vec::new() }); }
+    return out;
+}
+
+#[cfg(feature = "std")]
+pub fn tune<T: crate::Float>(n: usize) -> Tuned {
+    use crate::algorithms::{cooleytukey::{factorize, CooleyTukey}, CfftPlan, Shared};
+
+    if n < 2 { return Tuned { algorithm: Algorithm::Ct, factors: Vec::new() }; }
+    let mut best = (f64::INFINITY, None);
+    for tuned in candidates(n) {
+        let plan = match tuned.algorithm {
+            Algorithm::Ct => match CooleyTukey::<T>::with_factors(n, &tuned.factors) {
+                Ok(ct) => CfftPlan::Ct(Shared::new(ct)),
+                Err(_) => continue
+            },
+            algorithm => CfftPlan::with_algorithm(n, algorithm, &mut |m| Shared::new(CooleyTukey::new(m)))
+        };
+        let t = time(&plan);
+        if t < best.0 { best = (t, Some(tuned)); }
+    }
+    return best.1.unwrap_or(Tuned { algorithm: Algorithm::Ct, factors: factorize::<f64>(n).iter().map(|f| f.fct).collect() });
+}
+
+// seconds per transform. batches grow until they take a millisecond, best of three counts.
+// forward and backward take turns so the data stays the same size
+#[cfg(feature = "std")]
+fn time<T: crate::Float>(plan: &crate::CfftPlan<T>) -> f64 {
+    use crate::{Complex, Norm};
+    use std::time::{Duration, Instant};
+
+    let n = plan.len();
+    let mut data: Vec<Complex<T>> = (0..n).map(|i| Complex::new(T::from_f64((i % 7) as f64), T::from_f64((i % 3) as f64))).collect();
+    let mut scratch = alloc::vec![Complex::new(T::ZERO, T::ZERO); plan.scratch_len()];
+    let mut run = |reps: usize| {
+        let start = Instant::now();
+        for _ in 0..reps {
+            let _ = plan.forward_with_scratch(&mut data, &mut scratch, Norm::Ortho);
+            let _ = plan.backward_with_scratch(&mut data, &mut scratch, Norm::Ortho);
+        }
+        return start.elapsed();
+    };
+
+    let mut reps = 1;
+    while run(reps) < Duration::from_millis(1) && reps < 1 << 20 { reps *= 2; }
+    let best = (0..3).map(|_| run(reps)).min().unwrap_or_default();
+    return best.as_secs_f64() / (2 * reps) as f64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ct(factors: &[usize]) -> Tuned { Tuned { algorithm: Algorithm::Ct, factors: factors.to_vec() } }
+
+    #[test]
+    fn round_trip() {
+        let wisdom = BTreeMap::from([
+            (1, ct(&[])), (1000, ct(&[4, 2, 5, 5, 5])), (2310, Tuned { algorithm: Algorithm::Gt, factors: Vec::new() }),
+            (4099, Tuned { algorithm: Algorithm::Rd, factors: Vec::new() }), (4097, Tuned { algorithm: Algorithm::Bs, factors: Vec::new() })
+        ]);
+        let text = export::<f64>(&wisdom);
+        assert!(text.starts_with("palmfft wisdom 1\nf64 1 ct\nf64 1000 ct 4 2 5 5 5\n"));
+        assert_eq!(import::<f64>(&text).unwrap(), wisdom.into_iter().collect::<Vec<_>>());
+        // the other precision's lines are skipped, not an error
+        assert_eq!(import::<f32>(&text).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn malformed() {
+        for text in [
+            "", "palmfft wisdom 2\nf64 8 ct 4 2", "palmfft wisdom 1\nf64 8", "palmfft wisdom 1\nf64 8 xx",
+            "palmfft wisdom 1\nf64 eight ct 4 2", "palmfft wisdom 1\nf64 8 ct 4 4", "palmfft wisdom 1\nf64 8 ct 8",
+            "palmfft wisdom 1\nf64 9 rd", "palmfft wisdom 1\nf64 16 gt", "palmfft wisdom 1\nf64 7 rd 7"
+        ] {
+            assert!(matches!(import::<f64>(text), Err(FftError::InvalidParameter(_))), "{:?}", text);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn candidates_apply_to_the_length() {
+        for n in [0, 1] { assert_eq!(candidates(n), alloc::vec![ct(&[])]); }
+        for n in [2, 12, 97, 1009, 2018, 4096] {
+            for tuned in candidates(n) {
+                check_algorithm(n, tuned.algorithm).unwrap();
+                if tuned.algorithm == Algorithm::Ct { check_factors(n, &tuned.factors).unwrap(); }
+            }
+        }
+        assert!(candidates(1009).iter().any(|t| t.algorithm == Algorithm::Rd));
+        assert!(candidates(2018).iter().any(|t| t.algorithm == Algorithm::Gt));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn measuring_planner() {
+        use crate::{testing::{complex_signal, dft, err}, Norm, RcPlanner};
+
+        let mut planner = RcPlanner::<f64>::measuring();
+        for n in [0, 1, 60] {
+            let plan = planner.plan(n);
+            let x = complex_signal(n, 1);
+            let mut y = x.clone();
+            plan.forward(&mut y, Norm::Backward).unwrap();
+            if n > 0 { assert!(err(&y, &dft(&x, -1)) < 1e-13); }
+        }
+        let text = planner.export_wisdom();
+        assert!(text.contains("\nf64 0 ct\n") && text.contains("\nf64 1 ct\n") && text.contains("\nf64 60 "), "{}", text);
+
+        let mut other = RcPlanner::<f64>::new();
+        assert_eq!(other.import_wisdom(&text).unwrap(), 3);
+        assert_eq!(other.export_wisdom(), text);
+    }
+}